@@ -0,0 +1,184 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::{AocError, Solution};
+
+/// The known correct answers for the inputs in the data directory, read from a file like:
+///
+/// ```toml
+/// [day01]
+/// part1 = 142
+/// part2 = 281
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct KnownAnswers {
+    answers: BTreeMap<(u16, u8), usize>,
+}
+
+impl KnownAnswers {
+    pub fn load(path: &Path) -> Result<KnownAnswers, AocError> {
+        let contents = fs::read_to_string(path).map_err(|source| AocError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        KnownAnswers::parse(&contents).map_err(|(line, reason)| {
+            AocError::Config(format!(
+                "Invalid answers file '{}' at line {line}: {reason}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Parse the contents of an answers file, returning the line number and reason on error.
+    pub fn parse(contents: &str) -> Result<KnownAnswers, (usize, String)> {
+        let mut known_answers = KnownAnswers::default();
+        let mut day = None;
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            } else if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                day = Some(
+                    section
+                        .strip_prefix("day")
+                        .and_then(|d| d.parse().ok())
+                        .ok_or_else(|| (i + 1, format!("expected [dayNN], found [{section}]")))?,
+                );
+            } else {
+                let (key, value) = line.split_once('=').ok_or_else(|| {
+                    (
+                        i + 1,
+                        format!("expected 'partN = <answer>', found '{line}'"),
+                    )
+                })?;
+                let day =
+                    day.ok_or_else(|| (i + 1, String::from("answer is not in a [dayNN] section")))?;
+                let part = match key.trim() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    key => return Err((i + 1, format!("expected part1 or part2, found '{key}'"))),
+                };
+                let answer = value
+                    .trim()
+                    .parse()
+                    .map_err(|e| (i + 1, format!("invalid answer '{}': {e}", value.trim())))?;
+                known_answers.insert(day, part, answer);
+            }
+        }
+        Ok(known_answers)
+    }
+
+    pub fn get(&self, day: u16, part: u8) -> Option<usize> {
+        self.answers.get(&(day, part)).copied()
+    }
+
+    pub fn insert(&mut self, day: u16, part: u8, answer: usize) {
+        self.answers.insert((day, part), answer);
+    }
+}
+
+/// The result of checking a part's answer against the known answer.
+#[derive(Debug)]
+pub enum Verification {
+    Correct(usize),
+    Incorrect {
+        expected: usize,
+        actual: usize,
+    },
+    /// There is no known answer to check against
+    Unknown(usize),
+    /// Solving failed with the given error
+    Failed(String),
+}
+
+impl Verification {
+    /// Whether this is a regression, either a wrong answer or a failure to get one.
+    pub fn is_regression(&self) -> bool {
+        matches!(
+            self,
+            Verification::Incorrect { .. } | Verification::Failed(_)
+        )
+    }
+}
+
+/// Solve both parts of `solution` for `input` and check them against the known answers.
+pub fn verify(
+    solution: &dyn Solution,
+    input: &str,
+    known_answers: &KnownAnswers,
+) -> [Verification; 2] {
+    let parsed = match solution.parse(input) {
+        Ok(parsed) => parsed,
+        Err(e) => return [(); 2].map(|_| Verification::Failed(e.to_string())),
+    };
+    [(1, solution.part1(&parsed)), (2, solution.part2(&parsed))].map(|(part, answer)| {
+        match (answer, known_answers.get(solution.day(), part)) {
+            (Err(e), _) => Verification::Failed(e.to_string()),
+            (Ok(actual), None) => Verification::Unknown(actual),
+            (Ok(actual), Some(expected)) if actual == expected => Verification::Correct(actual),
+            (Ok(actual), Some(expected)) => Verification::Incorrect { expected, actual },
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::{verify, KnownAnswers, Verification};
+    use crate::day06::Day06;
+    use crate::fixtures::examples;
+    use crate::Puzzle;
+
+    #[test]
+    fn test_parse() {
+        let known_answers = KnownAnswers::parse(
+            "# Answers for my inputs
+[day01]
+part1 = 142
+part2 = 281
+
+[day06]
+part2 = 71503 # the long race
+",
+        )
+        .unwrap();
+        assert_eq!(Some(142), known_answers.get(1, 1));
+        assert_eq!(Some(281), known_answers.get(1, 2));
+        assert_eq!(None, known_answers.get(6, 1));
+        assert_eq!(Some(71503), known_answers.get(6, 2));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(1, KnownAnswers::parse("part1 = 3").unwrap_err().0);
+        assert_eq!(2, KnownAnswers::parse("[day01]\npart3 = 3").unwrap_err().0);
+        assert_eq!(
+            3,
+            KnownAnswers::parse("[day01]\n\npart1 = abc").unwrap_err().0
+        );
+        assert_eq!(1, KnownAnswers::parse("[one]").unwrap_err().0);
+    }
+
+    #[test]
+    fn test_verify() {
+        let (input, _) = &examples(Day06::YEAR, Day06::DAY, 1)[0];
+        let mut known_answers = KnownAnswers::default();
+        known_answers.insert(6, 1, 287);
+        let [part1, part2] = verify(&Day06, input, &known_answers);
+        assert!(matches!(
+            part1,
+            Verification::Incorrect {
+                expected: 287,
+                actual: 288
+            }
+        ));
+        assert!(part1.is_regression());
+        assert!(matches!(part2, Verification::Unknown(71503)));
+        assert!(!part2.is_regression());
+
+        known_answers.insert(6, 1, 288);
+        let [part1, _] = verify(&Day06, input, &known_answers);
+        assert!(matches!(part1, Verification::Correct(288)));
+
+        let [part1, part2] = verify(&Day06, "Time: 7 15 30", &known_answers);
+        assert!(part1.is_regression() && part2.is_regression());
+    }
+}
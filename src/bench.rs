@@ -0,0 +1,325 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    hint::black_box,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{AocError, Solution};
+
+/// How much slower than the saved median a stage's median can be before it is flagged.
+pub const REGRESSION_TOLERANCE: f64 = 0.25;
+
+/// A separately timed step of solving a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Parse, Stage::Part1, Stage::Part2];
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        })
+    }
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            "part1" => Ok(Stage::Part1),
+            "part2" => Ok(Stage::Part2),
+            _ => Err(format!("unknown stage '{s}'")),
+        }
+    }
+}
+
+/// Summary statistics of the time taken over a number of iterations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarise the samples, which must not be empty.
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = match sorted.len() % 2 {
+            0 => (sorted[middle - 1] + sorted[middle]) / 2,
+            _ => sorted[middle],
+        };
+        let secs = sorted.iter().map(Duration::as_secs_f64);
+        let mean = secs.clone().sum::<f64>() / sorted.len() as f64;
+        let variance = secs.map(|s| (s - mean).powi(2)).sum::<f64>() / sorted.len() as f64;
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+
+    /// Whether this is slower than `baseline` by more than the regression tolerance.
+    pub fn is_regression_from(&self, baseline: &Stats) -> bool {
+        self.median.as_secs_f64() > baseline.median.as_secs_f64() * (1.0 + REGRESSION_TOLERANCE)
+    }
+}
+
+/// Time parsing `input` and solving both parts, `iterations` times after `warmup` untimed runs.
+/// Returns the stats for each stage in the order of [`Stage::ALL`].
+pub fn bench(
+    solution: &dyn Solution,
+    input: &str,
+    warmup: usize,
+    iterations: usize,
+) -> Result<[Stats; 3], AocError> {
+    let mut samples = [(); 3].map(|_| Vec::with_capacity(iterations));
+    for i in 0..warmup + iterations {
+        let start = Instant::now();
+        let parsed = black_box(solution.parse(black_box(input))?);
+        let parse_time = start.elapsed();
+
+        let start = Instant::now();
+        black_box(solution.part1(&parsed)?);
+        let part1_time = start.elapsed();
+
+        let start = Instant::now();
+        black_box(solution.part2(&parsed)?);
+        let part2_time = start.elapsed();
+
+        if i >= warmup {
+            for (stage_samples, time) in
+                samples.iter_mut().zip([parse_time, part1_time, part2_time])
+            {
+                stage_samples.push(time);
+            }
+        }
+    }
+    Ok(samples.map(|stage_samples| Stats::from_samples(&stage_samples)))
+}
+
+/// Time each of the named implementations of each part of `solution` on `input`, like [`bench`],
+/// parsing the input once. Returns the part, name and stats of each in the order they're listed.
+pub fn bench_implementations(
+    solution: &dyn Solution,
+    input: &str,
+    warmup: usize,
+    iterations: usize,
+) -> Result<Vec<(u8, &'static str, Stats)>, AocError> {
+    let parsed = solution.parse(input)?;
+    let mut results = Vec::new();
+    for part in [1, 2] {
+        for name in solution.implementations(part) {
+            let mut samples = Vec::with_capacity(iterations);
+            for i in 0..warmup + iterations {
+                let start = Instant::now();
+                black_box(solution.solve_with(part, name, &parsed).transpose()?);
+                if i >= warmup {
+                    samples.push(start.elapsed());
+                }
+            }
+            results.push((part, name, Stats::from_samples(&samples)));
+        }
+    }
+    Ok(results)
+}
+
+/// Saved benchmark results, kept in a file with one tab-separated
+/// `year day stage min median mean stddev` entry per line, with times in nanoseconds.
+pub struct BenchRecord {
+    path: PathBuf,
+    results: BTreeMap<(u16, u16, Stage), Stats>,
+}
+
+impl BenchRecord {
+    /// Load the results saved at `path`. A missing file is treated as having no results.
+    pub fn load(path: &Path) -> Result<BenchRecord, AocError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(source) => {
+                return Err(AocError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        let results = contents
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                line_to_result(line).ok_or_else(|| {
+                    AocError::Config(format!(
+                        "Invalid entry in benchmark results '{}' at line {}: '{line}'",
+                        path.display(),
+                        i + 1
+                    ))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(BenchRecord {
+            path: path.to_path_buf(),
+            results,
+        })
+    }
+
+    pub fn get(&self, year: u16, day: u16, stage: Stage) -> Option<&Stats> {
+        self.results.get(&(year, day, stage))
+    }
+
+    /// Replace the results for a stage, which are kept until the record is saved.
+    pub fn insert(&mut self, year: u16, day: u16, stage: Stage, stats: Stats) {
+        self.results.insert((year, day, stage), stats);
+    }
+
+    /// Write all results to the file, replacing its contents.
+    pub fn save(&self) -> Result<(), AocError> {
+        let io_error = |source| AocError::Io {
+            path: self.path.clone(),
+            source,
+        };
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let contents: String = self
+            .results
+            .iter()
+            .map(|((year, day, stage), stats)| {
+                format!(
+                    "{year}\t{day}\t{stage}\t{}\t{}\t{}\t{}\n",
+                    stats.min.as_nanos(),
+                    stats.median.as_nanos(),
+                    stats.mean.as_nanos(),
+                    stats.stddev.as_nanos()
+                )
+            })
+            .collect();
+        fs::write(&self.path, contents).map_err(io_error)
+    }
+}
+
+fn line_to_result(line: &str) -> Option<((u16, u16, Stage), Stats)> {
+    let mut fields = line.split('\t');
+    let year = fields.next()?.parse().ok()?;
+    let day = fields.next()?.parse().ok()?;
+    let stage = fields.next()?.parse().ok()?;
+    let mut nanos = || fields.next()?.parse().ok().map(Duration::from_nanos);
+    let stats = Stats {
+        min: nanos()?,
+        median: nanos()?,
+        mean: nanos()?,
+        stddev: nanos()?,
+    };
+    fields
+        .next()
+        .is_none()
+        .then_some(((year, day, stage), stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, time::Duration};
+
+    use crate::bench::{bench, bench_implementations, BenchRecord, Stage, Stats};
+    use crate::day05::Day05;
+    use crate::day06::Day06;
+    use crate::fixtures::examples;
+    use crate::Puzzle;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = Stats::from_samples(&millis(&[4, 2, 8, 6]));
+        assert_eq!(Duration::from_millis(2), stats.min);
+        assert_eq!(Duration::from_millis(5), stats.median);
+        assert_eq!(Duration::from_millis(5), stats.mean);
+        assert_eq!(2236, stats.stddev.as_micros());
+
+        let stats = Stats::from_samples(&millis(&[3, 1, 9]));
+        assert_eq!(Duration::from_millis(3), stats.median);
+    }
+
+    #[test]
+    fn test_is_regression_from() {
+        let baseline = Stats::from_samples(&millis(&[100]));
+        assert!(!Stats::from_samples(&millis(&[90])).is_regression_from(&baseline));
+        assert!(!Stats::from_samples(&millis(&[120])).is_regression_from(&baseline));
+        assert!(Stats::from_samples(&millis(&[130])).is_regression_from(&baseline));
+    }
+
+    #[test]
+    fn test_bench() {
+        let (input, _) = &examples(Day06::YEAR, Day06::DAY, 1)[0];
+        let stats = bench(&Day06, input, 1, 5).unwrap();
+        for stage_stats in stats {
+            assert!(stage_stats.min <= stage_stats.median);
+        }
+        assert!(bench(&Day06, "Time: 7 15 30", 1, 5).is_err());
+    }
+
+    #[test]
+    fn test_bench_implementations() {
+        let (input, _) = &examples(Day06::YEAR, Day06::DAY, 1)[0];
+        let results = bench_implementations(&Day06, input, 1, 5).unwrap();
+        let names: Vec<_> = results
+            .iter()
+            .map(|&(part, name, _)| (part, name))
+            .collect();
+        assert_eq!(
+            vec![
+                (1, "brute"),
+                (1, "scan"),
+                (1, "closed-form"),
+                (2, "brute"),
+                (2, "scan"),
+                (2, "closed-form")
+            ],
+            names
+        );
+        assert!(bench_implementations(&Day06, "Time: 7 15 30", 1, 5).is_err());
+        assert!(bench_implementations(&Day05, "seeds: 1 2", 1, 5)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_bench_record_round_trip() {
+        let path = env::temp_dir().join(format!("aoc2023-bench-{}.txt", std::process::id()));
+        let mut record = BenchRecord::load(&path).unwrap();
+        assert_eq!(None, record.get(2023, 6, Stage::Part2));
+
+        let stats = Stats::from_samples(&millis(&[4, 2, 8, 6]));
+        record.insert(2023, 6, Stage::Part2, stats);
+        record.insert(2023, 6, Stage::Parse, stats);
+        record.save().unwrap();
+
+        let record = BenchRecord::load(&path).unwrap();
+        assert_eq!(Some(&stats), record.get(2023, 6, Stage::Part2));
+        assert_eq!(Some(&stats), record.get(2023, 6, Stage::Parse));
+        assert_eq!(None, record.get(2023, 6, Stage::Part1));
+        assert_eq!(None, record.get(2022, 6, Stage::Part2));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,994 @@
+use std::{ops::RangeInclusive, path::PathBuf};
+
+use crate::{
+    input::InputSource,
+    trace::{Filter, Level},
+    AocError, DEFAULT_YEAR,
+};
+
+const DEFAULT_ITERATIONS: usize = 10;
+const DEFAULT_WARMUP: usize = 2;
+const DEFAULT_SEED: u64 = 0;
+const DEFAULT_SIZE: usize = 100;
+
+pub const USAGE: &str = "Usage:
+  aoc2023 [DAY] [--input <PATH> | -] [--format <FORMAT>] [--impl <NAME>]
+                                      Run the puzzle for DAY, or the most recent day
+  aoc2023 all | <FIRST>-<LAST> [--format <FORMAT>] [--jobs <N>]
+                                      Run every day, or the days from FIRST to LAST
+  aoc2023 fetch <DAY>                 Download the input for DAY into the data directory
+  aoc2023 submit <DAY> <PART>         Solve PART of DAY and submit the answer
+  aoc2023 verify                      Check every day against the known answers in answers.toml
+  aoc2023 bench <DAY> [OPTIONS]       Time parsing and solving each part of DAY, and each
+                                      implementation of the parts that have several
+  aoc2023 new <DAY>                   Generate and register a module for DAY from a template
+  aoc2023 examples <DAY> <PAGE>       Save the examples in a saved puzzle page as test fixtures
+  aoc2023 watch <DAY>                 Re-run DAY and its tests when its source, input or
+                                      fixtures change
+  aoc2023 generate <DAY> [OPTIONS]    Print a random input for DAY
+  aoc2023 render <DAY> [OPTIONS]      Print the input for DAY annotated with colours, for days
+                                      that can show how they read it
+
+Options:
+  --year <YEAR>   Use the puzzles, inputs and answers of YEAR (default: 2023)
+  -v, -vv, -vvv   Print more of what the solvers are doing, to stderr
+  --trace <DAY>   Print everything the solver for DAY is doing, or only what -v asks
+                  for, to stderr. May be given more than once
+  --input <PATH>  Read the puzzle input from PATH instead of the data directory
+  -               Read the puzzle input from stdin
+  --format <FORMAT>
+                  Print answers as text (default) or as json records
+  --jobs <N>      Run days and parts on N threads at once when running several days
+  --impl <NAME>   Solve each part of a day that has an implementation called NAME with it,
+                  like brute, scan or closed-form for day 6
+
+Bench options:
+  --iterations <N>  Number of timed runs (default: 10)
+  --warmup <N>      Number of untimed runs before timing (default: 2)
+  --save <PATH>     Save the results to PATH, keeping results for other days
+  --compare <PATH>  Flag stages more than 25% slower than the results saved in PATH
+
+Generate options:
+  --seed <N>        Seed for the random numbers, which gives the same input every time
+                    (default: 0)
+  --size <N>        Roughly how many lines, items or maps to generate (default: 100)
+
+Render options:
+  --rows <A-B>      Only print lines A to B of the input, counting from 1
+  --cols <A-B>      Only print columns A to B of the input, counting from 1
+
+Environment:
+  AOC_DATA_DIR    Directory with a directory for each year to read and cache
+                  dayNN.txt inputs in, and to keep the ledger.txt of submitted
+                  answers and answers.toml of known answers in (default: ./data)
+  AOC_SESSION     Session cookie for adventofcode.com (default: read from
+                  ~/.config/aoc2023/session)
+  AOC_BASE_URL    Base URL of the Advent of Code site (default: https://adventofcode.com)";
+
+/// How answers are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    /// An array of records with the day, part, answer, duration and error of each part
+    Json,
+}
+
+/// A parsed command line, with the options that apply to every command.
+#[derive(Debug, PartialEq)]
+pub struct Cli {
+    pub year: u16,
+    /// Which trace messages from the solvers to print, if any
+    pub trace: Option<Filter>,
+    pub command: Command,
+}
+
+impl Cli {
+    /// Parse arguments, not including the program name. `--year`, `--trace` and `-v` may be
+    /// given anywhere.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Cli, AocError> {
+        let mut year = None;
+        let mut verbosity = 0;
+        let mut trace_days = Vec::new();
+        let mut rest = Vec::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--year" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| usage_error("--year requires a year"))?;
+                    if year.replace(parse_year(&value)?).is_some() {
+                        return Err(usage_error("only one year may be given"));
+                    }
+                }
+                "--trace" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| usage_error("--trace requires a day"))?;
+                    let day = value.strip_prefix("day").unwrap_or(&value);
+                    trace_days.push(parse_day(day)?);
+                }
+                _ if arg.len() > 1
+                    && arg
+                        .strip_prefix('-')
+                        .is_some_and(|v| v.bytes().all(|b| b == b'v')) =>
+                {
+                    verbosity += arg.len() - 1;
+                }
+                _ => rest.push(arg),
+            }
+        }
+        // Tracing a day shows everything for it unless -v asks for less
+        let level = match trace_days.is_empty() {
+            true => Level::from_verbosity(verbosity),
+            false => Level::from_verbosity(verbosity).or(Some(Level::Trace)),
+        };
+        Ok(Cli {
+            year: year.unwrap_or(DEFAULT_YEAR),
+            trace: level.map(|level| Filter {
+                level,
+                days: trace_days,
+            }),
+            command: Command::parse(rest)?,
+        })
+    }
+}
+
+/// A parsed command, not including the year.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Run the puzzle for a single day.
+    Run {
+        /// The day argument as given, if any
+        day: Option<String>,
+        /// Where to read the input from, if not the day's file in the data directory
+        input: Option<InputSource>,
+        format: Format,
+        /// The name of the implementation to solve each part that has one with
+        implementation: Option<String>,
+    },
+    /// Run every day in a range, reading inputs from the data directory.
+    RunAll {
+        days: RangeInclusive<u16>,
+        format: Format,
+        /// Number of threads to run days and parts on, if not one after another
+        jobs: Option<usize>,
+    },
+    /// Download and cache the input for a day.
+    Fetch { day: u16 },
+    /// Solve a part of a day and submit the answer.
+    Submit { day: u16, part: u8 },
+    /// Generate the module for a new day.
+    New { day: u16 },
+    /// Extract the examples from a saved puzzle page into fixtures for a day.
+    Examples { day: u16, page: PathBuf },
+    /// Check every day's answers against the known answers.
+    Verify,
+    /// Re-run a day and its tests whenever its source, input or fixtures change.
+    Watch { day: u16 },
+    /// Generate a random input for a day.
+    Generate { day: u16, seed: u64, size: usize },
+    /// Print a day's parsed input annotated for debugging.
+    Render {
+        day: u16,
+        /// Where to read the input from, if not the day's file in the data directory
+        input: Option<InputSource>,
+        /// The rows to print, counting from 0
+        rows: RangeInclusive<usize>,
+        /// The columns to print, counting from 0
+        cols: RangeInclusive<usize>,
+    },
+    /// Time parsing and solving each part of a day.
+    Bench {
+        day: u16,
+        /// Where to read the input from, if not the day's file in the data directory
+        input: Option<InputSource>,
+        /// Number of timed runs
+        iterations: usize,
+        /// Number of untimed runs before timing
+        warmup: usize,
+        /// Where to save the results
+        save: Option<PathBuf>,
+        /// Saved results to compare against
+        compare: Option<PathBuf>,
+    },
+}
+
+impl Command {
+    /// Parse arguments, not including the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, AocError> {
+        let mut args = args.into_iter().peekable();
+        match args.peek().map(String::as_str) {
+            Some("fetch") => {
+                args.next();
+                let day = args
+                    .next()
+                    .ok_or_else(|| usage_error("fetch requires a day"))?;
+                let command = Command::Fetch {
+                    day: parse_day(&day)?,
+                };
+                expect_no_more_args(args)?;
+                Ok(command)
+            }
+            Some("submit") => {
+                args.next();
+                let (day, part) = match (args.next(), args.next()) {
+                    (Some(day), Some(part)) => (day, part),
+                    _ => return Err(usage_error("submit requires a day and a part")),
+                };
+                let command = Command::Submit {
+                    day: parse_day(&day)?,
+                    part: parse_part(&part)?,
+                };
+                expect_no_more_args(args)?;
+                Ok(command)
+            }
+            Some("new") => {
+                args.next();
+                let day = args
+                    .next()
+                    .ok_or_else(|| usage_error("new requires a day"))?;
+                let command = Command::New {
+                    day: parse_day(&day)?,
+                };
+                expect_no_more_args(args)?;
+                Ok(command)
+            }
+            Some("examples") => {
+                args.next();
+                let (day, page) = match (args.next(), args.next()) {
+                    (Some(day), Some(page)) => (day, page),
+                    _ => return Err(usage_error("examples requires a day and a page")),
+                };
+                let command = Command::Examples {
+                    day: parse_day(&day)?,
+                    page: PathBuf::from(page),
+                };
+                expect_no_more_args(args)?;
+                Ok(command)
+            }
+            Some("watch") => {
+                args.next();
+                let day = args
+                    .next()
+                    .ok_or_else(|| usage_error("watch requires a day"))?;
+                let command = Command::Watch {
+                    day: parse_day(&day)?,
+                };
+                expect_no_more_args(args)?;
+                Ok(command)
+            }
+            Some("generate") => {
+                args.next();
+                parse_generate(args)
+            }
+            Some("render") => {
+                args.next();
+                parse_render(args)
+            }
+            Some("verify") => {
+                args.next();
+                expect_no_more_args(args)?;
+                Ok(Command::Verify)
+            }
+            Some("bench") => {
+                args.next();
+                parse_bench(args)
+            }
+            _ => parse_run(args),
+        }
+    }
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<Command, AocError> {
+    let mut day = None;
+    let mut input = None;
+    let mut format = Format::default();
+    let mut jobs = None;
+    let mut implementation = None;
+    while let Some(arg) = args.next() {
+        let source = match arg.as_str() {
+            "--impl" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| usage_error("--impl requires an implementation name"))?;
+                implementation = Some(value);
+                continue;
+            }
+            "--jobs" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| usage_error("--jobs requires a number of threads"))?;
+                jobs = Some(parse_count(&arg, &value, 1)?);
+                continue;
+            }
+            "--format" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| usage_error("--format requires a format"))?;
+                format = match value.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    _ => {
+                        return Err(usage_error(format!(
+                            "unknown format '{value}', expected text or json"
+                        )))
+                    }
+                };
+                continue;
+            }
+            "--input" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| usage_error("--input requires a path"))?;
+                match path.as_str() {
+                    "-" => InputSource::Stdin,
+                    _ => InputSource::Path(PathBuf::from(path)),
+                }
+            }
+            "-" => InputSource::Stdin,
+            _ if arg.starts_with("--") => {
+                return Err(usage_error(format!("unknown option '{arg}'")))
+            }
+            _ if day.is_none() => {
+                day = Some(arg);
+                continue;
+            }
+            _ => return Err(usage_error(format!("unexpected argument '{arg}'"))),
+        };
+        if input.replace(source).is_some() {
+            return Err(usage_error("only one input may be given"));
+        }
+    }
+    match day.as_deref().map(parse_days).transpose()?.flatten() {
+        Some(_) if input.is_some() => Err(usage_error(
+            "an input can only be given when running a single day",
+        )),
+        Some(_) if implementation.is_some() => Err(usage_error(
+            "--impl can only be given when running a single day",
+        )),
+        Some(days) => Ok(Command::RunAll { days, format, jobs }),
+        None if jobs.is_some() => Err(usage_error(
+            "--jobs can only be given when running several days",
+        )),
+        None => Ok(Command::Run {
+            day,
+            input,
+            format,
+            implementation,
+        }),
+    }
+}
+
+/// Parse `all` or a range of days like `1-5`, returning `None` for anything else.
+fn parse_days(arg: &str) -> Result<Option<RangeInclusive<u16>>, AocError> {
+    if arg == "all" {
+        return Ok(Some(1..=25));
+    }
+    let Some((first, last)) = arg.split_once('-') else {
+        return Ok(None);
+    };
+    let (first, last) = (parse_day(first)?, parse_day(last)?);
+    if first > last {
+        return Err(usage_error(format!(
+            "'{arg}' is not a range of days, {first} is after {last}"
+        )));
+    }
+    Ok(Some(first..=last))
+}
+
+fn parse_bench(mut args: impl Iterator<Item = String>) -> Result<Command, AocError> {
+    let day = args
+        .next()
+        .ok_or_else(|| usage_error("bench requires a day"))?;
+    let day = parse_day(&day)?;
+    let mut input = None;
+    let mut iterations = DEFAULT_ITERATIONS;
+    let mut warmup = DEFAULT_WARMUP;
+    let mut save = None;
+    let mut compare = None;
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| usage_error(format!("{arg} requires a value")))
+        };
+        match arg.as_str() {
+            "--input" => {
+                input = Some(match value()?.as_str() {
+                    "-" => InputSource::Stdin,
+                    path => InputSource::Path(PathBuf::from(path)),
+                })
+            }
+            "-" => input = Some(InputSource::Stdin),
+            "--iterations" => iterations = parse_count(&arg, &value()?, 1)?,
+            "--warmup" => warmup = parse_count(&arg, &value()?, 0)?,
+            "--save" => save = Some(PathBuf::from(value()?)),
+            "--compare" => compare = Some(PathBuf::from(value()?)),
+            _ if arg.starts_with("--") => {
+                return Err(usage_error(format!("unknown option '{arg}'")))
+            }
+            _ => return Err(usage_error(format!("unexpected argument '{arg}'"))),
+        }
+    }
+    Ok(Command::Bench {
+        day,
+        input,
+        iterations,
+        warmup,
+        save,
+        compare,
+    })
+}
+
+fn parse_generate(mut args: impl Iterator<Item = String>) -> Result<Command, AocError> {
+    let day = args
+        .next()
+        .ok_or_else(|| usage_error("generate requires a day"))?;
+    let day = parse_day(&day)?;
+    let mut seed = DEFAULT_SEED;
+    let mut size = DEFAULT_SIZE;
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| usage_error(format!("{arg} requires a value")))
+        };
+        match arg.as_str() {
+            "--seed" => {
+                let value = value()?;
+                seed = value.parse().map_err(|_| {
+                    usage_error(format!("'{value}' is not a valid seed, expected a number"))
+                })?
+            }
+            "--size" => size = parse_count(&arg, &value()?, 1)?,
+            _ if arg.starts_with("--") => {
+                return Err(usage_error(format!("unknown option '{arg}'")))
+            }
+            _ => return Err(usage_error(format!("unexpected argument '{arg}'"))),
+        }
+    }
+    Ok(Command::Generate { day, seed, size })
+}
+
+fn parse_render(mut args: impl Iterator<Item = String>) -> Result<Command, AocError> {
+    let day = args
+        .next()
+        .ok_or_else(|| usage_error("render requires a day"))?;
+    let day = parse_day(&day)?;
+    let mut input = None;
+    let mut rows = 0..=usize::MAX;
+    let mut cols = 0..=usize::MAX;
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| usage_error(format!("{arg} requires a value")))
+        };
+        match arg.as_str() {
+            "--input" => {
+                input = Some(match value()?.as_str() {
+                    "-" => InputSource::Stdin,
+                    path => InputSource::Path(PathBuf::from(path)),
+                })
+            }
+            "-" => input = Some(InputSource::Stdin),
+            "--rows" => rows = parse_window(&arg, &value()?)?,
+            "--cols" => cols = parse_window(&arg, &value()?)?,
+            _ if arg.starts_with("--") => {
+                return Err(usage_error(format!("unknown option '{arg}'")))
+            }
+            _ => return Err(usage_error(format!("unexpected argument '{arg}'"))),
+        }
+    }
+    Ok(Command::Render {
+        day,
+        input,
+        rows,
+        cols,
+    })
+}
+
+/// Parse a range of lines or columns like `3-10`, counting from 1, into one counting from 0.
+fn parse_window(option: &str, arg: &str) -> Result<RangeInclusive<usize>, AocError> {
+    let invalid = || {
+        usage_error(format!(
+            "'{arg}' is not a valid range for {option}, expected <A-B> counting from 1"
+        ))
+    };
+    let (first, last) = arg.split_once('-').ok_or_else(invalid)?;
+    match (first.parse::<usize>(), last.parse::<usize>()) {
+        (Ok(first), Ok(last)) if 1 <= first && first <= last => Ok(first - 1..=last - 1),
+        _ => Err(invalid()),
+    }
+}
+
+fn parse_day(arg: &str) -> Result<u16, AocError> {
+    match arg.parse() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(usage_error(format!(
+            "'{arg}' is not a day, expected a number from 1 to 25"
+        ))),
+    }
+}
+
+fn parse_year(arg: &str) -> Result<u16, AocError> {
+    match arg.parse() {
+        Ok(year) if year >= 2015 => Ok(year),
+        _ => Err(usage_error(format!(
+            "'{arg}' is not a year of Advent of Code, expected 2015 or later"
+        ))),
+    }
+}
+
+fn parse_part(arg: &str) -> Result<u8, AocError> {
+    match arg {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(usage_error(format!(
+            "'{arg}' is not a part, expected 1 or 2"
+        ))),
+    }
+}
+
+fn parse_count(option: &str, arg: &str, min: usize) -> Result<usize, AocError> {
+    match arg.parse() {
+        Ok(count) if count >= min => Ok(count),
+        _ => Err(usage_error(format!(
+            "'{arg}' is not a valid count for {option}, expected a number of at least {min}"
+        ))),
+    }
+}
+
+fn expect_no_more_args(mut args: impl Iterator<Item = String>) -> Result<(), AocError> {
+    match args.next() {
+        Some(arg) => Err(usage_error(format!("unexpected argument '{arg}'"))),
+        None => Ok(()),
+    }
+}
+
+fn usage_error(message: impl Into<String>) -> AocError {
+    AocError::Usage(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::cli::{Cli, Command, Format};
+    use crate::input::InputSource;
+    use crate::trace::{Filter, Level};
+    use crate::AocError;
+
+    fn parse(args: &[&str]) -> Result<Command, AocError> {
+        Command::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    fn parse_cli(args: &[&str]) -> Result<Cli, AocError> {
+        Cli::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            Command::Run {
+                day: None,
+                input: None,
+                format: Format::Text,
+                implementation: None
+            },
+            parse(&[]).unwrap()
+        );
+        assert_eq!(
+            Command::Run {
+                day: Some(String::from("2")),
+                input: None,
+                format: Format::Text,
+                implementation: None
+            },
+            parse(&["2"]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_run_input() {
+        assert_eq!(
+            Command::Run {
+                day: Some(String::from("5")),
+                input: Some(InputSource::Path(PathBuf::from("stress/day05.txt"))),
+                format: Format::Text,
+                implementation: None
+            },
+            parse(&["5", "--input", "stress/day05.txt"]).unwrap()
+        );
+        assert_eq!(
+            Command::Run {
+                day: Some(String::from("5")),
+                input: Some(InputSource::Stdin),
+                format: Format::Text,
+                implementation: None
+            },
+            parse(&["5", "-"]).unwrap()
+        );
+        assert_eq!(
+            Command::Run {
+                day: Some(String::from("5")),
+                input: Some(InputSource::Stdin),
+                format: Format::Text,
+                implementation: None
+            },
+            parse(&["--input", "-", "5"]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_impl() {
+        assert_eq!(
+            Command::Run {
+                day: Some(String::from("6")),
+                input: None,
+                format: Format::Text,
+                implementation: Some(String::from("closed-form"))
+            },
+            parse(&["6", "--impl", "closed-form"]).unwrap()
+        );
+        assert!(matches!(parse(&["6", "--impl"]), Err(AocError::Usage(_))));
+        assert!(matches!(
+            parse(&["all", "--impl", "brute"]),
+            Err(AocError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_run_errors() {
+        assert!(matches!(parse(&["5", "--input"]), Err(AocError::Usage(_))));
+        assert!(matches!(parse(&["5", "-", "-"]), Err(AocError::Usage(_))));
+        assert!(matches!(
+            parse(&["5", "--verbose"]),
+            Err(AocError::Usage(_))
+        ));
+        assert!(matches!(parse(&["5", "6"]), Err(AocError::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_fetch() {
+        assert_eq!(Command::Fetch { day: 8 }, parse(&["fetch", "8"]).unwrap());
+        assert!(matches!(parse(&["fetch"]), Err(AocError::Usage(_))));
+        assert!(matches!(parse(&["fetch", "26"]), Err(AocError::Usage(_))));
+        assert!(matches!(
+            parse(&["fetch", "8", "9"]),
+            Err(AocError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_submit() {
+        assert_eq!(
+            Command::Submit { day: 8, part: 2 },
+            parse(&["submit", "8", "2"]).unwrap()
+        );
+        assert!(matches!(parse(&["submit", "8"]), Err(AocError::Usage(_))));
+        assert!(matches!(
+            parse(&["submit", "8", "3"]),
+            Err(AocError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(Command::New { day: 9 }, parse(&["new", "9"]).unwrap());
+        assert!(matches!(parse(&["new"]), Err(AocError::Usage(_))));
+        assert!(matches!(parse(&["new", "0"]), Err(AocError::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_examples() {
+        assert_eq!(
+            Command::Examples {
+                day: 8,
+                page: PathBuf::from("pages/day08.html")
+            },
+            parse(&["examples", "8", "pages/day08.html"]).unwrap()
+        );
+        assert!(matches!(parse(&["examples", "8"]), Err(AocError::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        assert_eq!(Command::Watch { day: 6 }, parse(&["watch", "6"]).unwrap());
+        assert!(matches!(parse(&["watch"]), Err(AocError::Usage(_))));
+        assert!(matches!(parse(&["watch", "26"]), Err(AocError::Usage(_))));
+        assert!(matches!(
+            parse(&["watch", "6", "7"]),
+            Err(AocError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(Command::Verify, parse(&["verify"]).unwrap());
+        assert!(matches!(parse(&["verify", "8"]), Err(AocError::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            Command::Bench {
+                day: 6,
+                input: None,
+                iterations: 10,
+                warmup: 2,
+                save: None,
+                compare: None
+            },
+            parse(&["bench", "6"]).unwrap()
+        );
+        assert_eq!(
+            Command::Bench {
+                day: 6,
+                input: Some(InputSource::Stdin),
+                iterations: 50,
+                warmup: 0,
+                save: Some(PathBuf::from("bench.txt")),
+                compare: Some(PathBuf::from("old.txt"))
+            },
+            parse(&[
+                "bench",
+                "6",
+                "--iterations",
+                "50",
+                "--warmup",
+                "0",
+                "--save",
+                "bench.txt",
+                "--compare",
+                "old.txt",
+                "-"
+            ])
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_render() {
+        assert_eq!(
+            Command::Render {
+                day: 3,
+                input: None,
+                rows: 0..=usize::MAX,
+                cols: 0..=usize::MAX
+            },
+            parse(&["render", "3"]).unwrap()
+        );
+        assert_eq!(
+            Command::Render {
+                day: 3,
+                input: Some(InputSource::Stdin),
+                rows: 9..=19,
+                cols: 0..=0
+            },
+            parse(&["render", "3", "--rows", "10-20", "-", "--cols", "1-1"]).unwrap()
+        );
+        for bad in [
+            &["render"][..],
+            &["render", "3", "--rows"],
+            &["render", "3", "--rows", "10"],
+            &["render", "3", "--rows", "0-5"],
+            &["render", "3", "--cols", "5-2"],
+            &["render", "3", "extra"],
+        ] {
+            assert!(matches!(parse(bad), Err(AocError::Usage(_))));
+        }
+    }
+
+    #[test]
+    fn test_parse_generate() {
+        assert_eq!(
+            Command::Generate {
+                day: 5,
+                seed: 0,
+                size: 100
+            },
+            parse(&["generate", "5"]).unwrap()
+        );
+        assert_eq!(
+            Command::Generate {
+                day: 5,
+                seed: 42,
+                size: 3000
+            },
+            parse(&["generate", "5", "--size", "3000", "--seed", "42"]).unwrap()
+        );
+        for bad in [
+            &["generate"][..],
+            &["generate", "5", "--seed", "-1"],
+            &["generate", "5", "--size", "0"],
+            &["generate", "5", "--size"],
+            &["generate", "5", "--count", "3"],
+        ] {
+            assert!(matches!(parse(bad), Err(AocError::Usage(_))));
+        }
+    }
+
+    #[test]
+    fn test_parse_bench_errors() {
+        assert!(matches!(parse(&["bench"]), Err(AocError::Usage(_))));
+        assert!(matches!(
+            parse(&["bench", "6", "--iterations", "0"]),
+            Err(AocError::Usage(_))
+        ));
+        assert!(matches!(
+            parse(&["bench", "6", "--warmup"]),
+            Err(AocError::Usage(_))
+        ));
+        assert!(matches!(
+            parse(&["bench", "6", "7"]),
+            Err(AocError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_run_all() {
+        assert_eq!(
+            Command::RunAll {
+                days: 1..=25,
+                format: Format::Text,
+                jobs: None
+            },
+            parse(&["all"]).unwrap()
+        );
+        assert_eq!(
+            Command::RunAll {
+                days: 1..=5,
+                format: Format::Text,
+                jobs: None
+            },
+            parse(&["1-5"]).unwrap()
+        );
+        assert_eq!(
+            Command::RunAll {
+                days: 3..=3,
+                format: Format::Text,
+                jobs: None
+            },
+            parse(&["3-3"]).unwrap()
+        );
+        assert!(matches!(parse(&["5-1"]), Err(AocError::Usage(_))));
+        assert!(matches!(parse(&["1-26"]), Err(AocError::Usage(_))));
+        assert!(matches!(parse(&["all", "-"]), Err(AocError::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_jobs() {
+        assert_eq!(
+            Command::RunAll {
+                days: 1..=25,
+                format: Format::Text,
+                jobs: Some(4)
+            },
+            parse(&["all", "--jobs", "4"]).unwrap()
+        );
+        assert!(matches!(
+            parse(&["all", "--jobs", "0"]),
+            Err(AocError::Usage(_))
+        ));
+        assert!(matches!(
+            parse(&["5", "--jobs", "4"]),
+            Err(AocError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(
+            Command::Run {
+                day: Some(String::from("5")),
+                input: None,
+                format: Format::Json,
+                implementation: None
+            },
+            parse(&["5", "--format", "json"]).unwrap()
+        );
+        assert_eq!(
+            Command::RunAll {
+                days: 1..=25,
+                format: Format::Json,
+                jobs: None
+            },
+            parse(&["--format", "json", "all"]).unwrap()
+        );
+        assert!(matches!(parse(&["5", "--format"]), Err(AocError::Usage(_))));
+        assert!(matches!(
+            parse(&["5", "--format", "yaml"]),
+            Err(AocError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_year() {
+        assert_eq!(
+            Cli {
+                year: 2023,
+                trace: None,
+                command: Command::Verify
+            },
+            parse_cli(&["verify"]).unwrap()
+        );
+        assert_eq!(
+            Cli {
+                year: 2022,
+                trace: None,
+                command: Command::Fetch { day: 3 }
+            },
+            parse_cli(&["fetch", "--year", "2022", "3"]).unwrap()
+        );
+        assert_eq!(
+            Cli {
+                year: 2015,
+                trace: None,
+                command: Command::RunAll {
+                    days: 1..=25,
+                    format: Format::Text,
+                    jobs: None
+                }
+            },
+            parse_cli(&["--year", "2015", "all"]).unwrap()
+        );
+
+        for bad in [
+            &["--year"][..],
+            &["--year", "2014"],
+            &["--year", "twenty"],
+            &["--year", "2022", "--year", "2023"],
+        ] {
+            assert!(matches!(parse_cli(bad), Err(AocError::Usage(_))));
+        }
+    }
+
+    #[test]
+    fn test_parse_trace() {
+        let filter = |level, days: &[u16]| {
+            Some(Filter {
+                level,
+                days: days.to_vec(),
+            })
+        };
+        assert_eq!(
+            filter(Level::Info, &[]),
+            parse_cli(&["-v", "6"]).unwrap().trace
+        );
+        assert_eq!(
+            filter(Level::Debug, &[]),
+            parse_cli(&["all", "-vv"]).unwrap().trace
+        );
+        assert_eq!(
+            filter(Level::Trace, &[]),
+            parse_cli(&["-v", "-vv"]).unwrap().trace
+        );
+        assert_eq!(
+            filter(Level::Trace, &[8, 2]),
+            parse_cli(&["--trace", "day08", "--trace", "2"])
+                .unwrap()
+                .trace
+        );
+        assert_eq!(
+            filter(Level::Info, &[8]),
+            parse_cli(&["--trace", "day08", "-v"]).unwrap().trace
+        );
+
+        // A lone dash still reads the input from stdin
+        let cli = parse_cli(&["6", "-"]).unwrap();
+        assert_eq!(None, cli.trace);
+        assert_eq!(
+            Command::Run {
+                day: Some(String::from("6")),
+                input: Some(InputSource::Stdin),
+                format: Format::Text,
+                implementation: None
+            },
+            cli.command
+        );
+
+        for bad in [&["--trace"][..], &["--trace", "day26"], &["--trace", "x"]] {
+            assert!(matches!(parse_cli(bad), Err(AocError::Usage(_))));
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{ledger::Outcome, AocError};
+
+/// Environment variable holding the session cookie used to authenticate with the site.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Environment variable that overrides the base URL of the site, e.g. to point at a local server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/cahrin/aoc2023";
+
+/// A client for the Advent of Code site.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Create a client using `AOC_BASE_URL` if set, and the session from `AOC_SESSION` or,
+    /// failing that, the session file in the config directory.
+    pub fn from_env() -> Result<Client, AocError> {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let session = match env::var(SESSION_ENV) {
+            Ok(session) => session,
+            Err(_) => {
+                let path = session_file().ok_or_else(|| {
+                    AocError::Config(format!(
+                        "{SESSION_ENV} is not set and no config directory was found"
+                    ))
+                })?;
+                fs::read_to_string(&path).map_err(|_| {
+                    AocError::Config(format!(
+                        "{SESSION_ENV} is not set and '{}' could not be read",
+                        path.display()
+                    ))
+                })?
+            }
+        };
+        Ok(Client::new(&base_url, &session))
+    }
+
+    /// Download the puzzle input for the given day.
+    pub fn fetch_input(&self, year: u16, day: u16) -> Result<String, AocError> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|e| request_error(&url, e))?;
+        response.into_string().map_err(|e| AocError::Request {
+            url,
+            reason: e.to_string(),
+        })
+    }
+
+    /// Submit an answer for a part of the given day.
+    pub fn submit_answer(
+        &self,
+        year: u16,
+        day: u16,
+        part: u8,
+        answer: usize,
+    ) -> Result<Outcome, AocError> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[
+                ("level", &part.to_string()),
+                ("answer", &answer.to_string()),
+            ])
+            .map_err(|e| request_error(&url, e))?;
+        let body = response.into_string().map_err(|e| AocError::Request {
+            url: url.clone(),
+            reason: e.to_string(),
+        })?;
+        Outcome::from_response(&body).ok_or_else(|| AocError::Request {
+            url,
+            reason: String::from("unrecognised response to answer"),
+        })
+    }
+}
+
+/// The file the session cookie is read from when `AOC_SESSION` is not set,
+/// `$XDG_CONFIG_HOME/aoc2023/session` or `~/.config/aoc2023/session`.
+pub fn session_file() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|config_dir| config_dir.join("aoc2023").join("session"))
+}
+
+/// Return the input cached at `path`, downloading and caching it first if it isn't there yet.
+/// Once an input is cached it is never requested again.
+pub fn fetch_input_cached(
+    client: &Client,
+    year: u16,
+    day: u16,
+    path: &Path,
+) -> Result<String, AocError> {
+    if path.exists() {
+        return fs::read_to_string(path).map_err(|source| AocError::Io {
+            path: path.to_path_buf(),
+            source,
+        });
+    }
+
+    let input = client.fetch_input(year, day)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|source| AocError::Io {
+            path: dir.to_path_buf(),
+            source,
+        })?;
+    }
+    fs::write(path, &input).map_err(|source| AocError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(input)
+}
+
+fn request_error(url: &str, error: ureq::Error) -> AocError {
+    let reason = match error {
+        ureq::Error::Status(status, response) => {
+            format!("server responded with {status} {}", response.status_text())
+        }
+        ureq::Error::Transport(transport) => transport.to_string(),
+    };
+    AocError::Request {
+        url: url.to_string(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use crate::client::{fetch_input_cached, Client};
+    use crate::test_server::TestServer;
+    use crate::AocError;
+
+    #[test]
+    fn test_fetch_input() {
+        let server = TestServer::start(|request| match request.path.as_str() {
+            "/2023/day/6/input" => (200, String::from("Time: 7\nDistance: 9\n")),
+            _ => (404, String::from("Not Found")),
+        });
+        let client = Client::new(server.url(), "abc123\n");
+
+        assert_eq!(
+            "Time: 7\nDistance: 9\n",
+            client.fetch_input(2023, 6).unwrap()
+        );
+        let requests = server.requests();
+        assert_eq!("GET", requests[0].method);
+        assert_eq!(Some("session=abc123"), requests[0].header("Cookie"));
+        assert!(requests[0].header("User-Agent").is_some());
+    }
+
+    #[test]
+    fn test_fetch_input_error_status() {
+        let server = TestServer::start(|_| (400, String::from("Please log in")));
+        let client = Client::new(server.url(), "expired");
+
+        match client.fetch_input(2023, 1) {
+            Err(AocError::Request { url, reason }) => {
+                assert!(url.ends_with("/2023/day/1/input"));
+                assert!(reason.contains("400"));
+            }
+            other => panic!("Unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_fetch_input_cached_requests_once() {
+        let server = TestServer::start(|_| (200, String::from("LLR\n")));
+        let client = Client::new(server.url(), "abc123");
+        let dir = env::temp_dir().join(format!("aoc2023-fetch-{}", std::process::id()));
+        let path = dir.join("day08.txt");
+
+        assert_eq!(
+            "LLR\n",
+            fetch_input_cached(&client, 2023, 8, &path).unwrap()
+        );
+        assert_eq!("LLR\n", fs::read_to_string(&path).unwrap());
+        assert_eq!(
+            "LLR\n",
+            fetch_input_cached(&client, 2023, 8, &path).unwrap()
+        );
+        assert_eq!(1, server.requests().len());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
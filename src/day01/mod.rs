@@ -1,19 +1,41 @@
-use crate::parse_file_input;
-
-pub fn run() {
-    let input = parse_file_input(1);
-    println!("Running Day 1");
-    println!(
-        "Part 1 :: What is the sum of all of the calibration values? --> {:?}",
-        part1(&input)
-    );
-    println!(
-        "Part 2 :: What is the sum of all of the calibration values? --> {:?}",
-        part2(&input)
-    );
+use crate::{rng::Rng, Answers, AocError, LineError, Puzzle};
+
+pub struct Day01;
+
+impl Puzzle for Day01 {
+    const YEAR: u16 = 2023;
+    const DAY: u16 = 1;
+    const TITLE: &'static str = "Trebuchet?!";
+    const QUESTIONS: [&'static str; 2] = [
+        "What is the sum of all of the calibration values?",
+        "What is the sum of all of the calibration values?",
+    ];
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+/// Solve both parts of the puzzle for `input`.
+pub fn solve(input: &str) -> Result<Answers, AocError> {
+    Day01::solve(input)
 }
 
-fn part1(input: &str) -> usize {
+fn part1(lines: &[String]) -> Result<usize, AocError> {
     let matchers: Vec<(&str, usize)> = vec![
         ("1", 1),
         ("2", 2),
@@ -26,13 +48,10 @@ fn part1(input: &str) -> usize {
         ("9", 9),
     ];
 
-    input
-        .lines()
-        .map(|l| calibration_value_for_line(l, &matchers))
-        .sum()
+    sum_calibration_values(lines, &matchers)
 }
 
-fn part2(input: &str) -> usize {
+fn part2(lines: &[String]) -> Result<usize, AocError> {
     let matchers: Vec<(&str, usize)> = vec![
         ("1", 1),
         ("one", 1),
@@ -54,83 +73,134 @@ fn part2(input: &str) -> usize {
         ("nine", 9),
     ];
 
-    input
-        .lines()
-        .map(|l| calibration_value_for_line(l, &matchers))
+    sum_calibration_values(lines, &matchers)
+}
+
+fn sum_calibration_values(lines: &[String], matchers: &[(&str, usize)]) -> Result<usize, AocError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            calibration_value_for_line(line, matchers)
+                .ok_or_else(|| LineError::new(line, line, "no digit found").at(Day01::DAY, i + 1))
+        })
         .sum()
 }
 
-fn calibration_value_for_line(line: &str, matchers: &[(&str, usize)]) -> usize {
+fn calibration_value_for_line(line: &str, matchers: &[(&str, usize)]) -> Option<usize> {
     // Find the first instance of each matcher in the line, then reduce to the one with the lowest index
     let first = matchers
         .iter()
         .filter_map(|(string_to_match, val)| line.find(string_to_match).map(|i| (i, val)))
-        .reduce(|a, b| if a.0 < b.0 { a } else { b })
-        .expect("Failed to find first digit in {l}")
+        .reduce(|a, b| if a.0 < b.0 { a } else { b })?
         .1;
 
     // Find the last instance of each matcher in the line, then reduce to the one with the highest index
     let last = matchers
         .iter()
         .filter_map(|(string_to_match, val)| line.rfind(string_to_match).map(|i| (i, val)))
-        .reduce(|a, b| if a.0 > b.0 { a } else { b })
-        .expect("Failed to find the last digit in {l}")
+        .reduce(|a, b| if a.0 > b.0 { a } else { b })?
         .1;
 
     // Combine the first and last to make one two-digit number
-    10 * first + last
+    Some(10 * first + last)
+}
+
+/// A line per calibration value, each a mix of letters, digits and spelled out digits with at
+/// least one digit.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    let mut input = String::new();
+    for _ in 0..size {
+        let tokens = rng.range(1..=8);
+        let digit_at = rng.below(tokens);
+        for i in 0..tokens {
+            match rng.below(3) {
+                _ if i == digit_at => input.push(char::from(b'1' + rng.below(9) as u8)),
+                0 => input.push(char::from(b'0' + rng.below(10) as u8)),
+                1 => input.push_str(rng.choose::<&str>(&WORDS)),
+                _ => {
+                    for _ in 0..rng.range(1..=4) {
+                        input.push(char::from(b'a' + rng.below(26) as u8));
+                    }
+                }
+            }
+        }
+        input.push('\n');
+    }
+    input
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day01::{part1, part2};
+    use crate::day01::{generate, part1, part2, Day01};
+    use crate::fixtures::examples;
+    use crate::rng::Rng;
+    use crate::{AocError, Puzzle};
 
     #[test]
     fn test_part1_sample_input() {
-        assert_eq!(
-            part1(
-                "1abc2
-        pqr3stu8vwx
-        a1b2c3d4e5f
-        treb7uchet"
-            ),
-            142
-        );
+        for (input, answer) in examples(Day01::YEAR, Day01::DAY, 1) {
+            assert_eq!(answer, part1(&Day01::parse(&input).unwrap()).unwrap());
+        }
     }
 
     #[test]
     fn test_part2_sample_input() {
-        assert_eq!(
-            part2(
-                "two1nine
-        eightwothree
-        abcone2threexyz
-        xtwone3four
-        4nineeightseven2
-        zoneight234
-        7pqrstsixteen"
-            ),
-            281
-        );
+        for (input, answer) in examples(Day01::YEAR, Day01::DAY, 2) {
+            assert_eq!(answer, part2(&Day01::parse(&input).unwrap()).unwrap());
+        }
     }
 
     #[test]
     fn test_part2_overlapping_strings() {
         assert_eq!(
             part2(
-                "eighthree
+                &Day01::parse(
+                    "eighthree
                 sevenine
                 oneight"
-            ),
+                )
+                .unwrap()
+            )
+            .unwrap(),
             83 + 79 + 18
         );
         assert_eq!(
             part2(
-                "eighthree
+                &Day01::parse(
+                    "eighthree
                 sevenine4
                 4oneight"
-            ),
+                )
+                .unwrap()
+            )
+            .unwrap(),
             83 + 74 + 48
         );
     }
+
+    #[test]
+    fn test_part1_line_without_digit() {
+        let lines = Day01::parse("1abc2\npqrstuvwx\na1b2c3d4e5f").unwrap();
+        assert!(matches!(
+            part1(&lines),
+            Err(AocError::Malformed {
+                day: 1,
+                line: 2,
+                column: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 50);
+        assert_eq!(input, generate(&mut Rng::new(1), 50));
+        assert_eq!(50, input.lines().count());
+        Day01::solve(&input).unwrap();
+    }
 }
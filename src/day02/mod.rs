@@ -1,22 +1,41 @@
-use crate::parse_file_input;
-
-pub fn run() {
-    let input = parse_file_input(2);
-    println!("Running Day 2");
-    println!(
-        "Part 1 :: What is the sum of the IDs of those games? --> {:?}",
-        part1(&input)
-    );
-    println!(
-        "Part 2 :: What is the sum of the power of these sets? --> {:?}",
-        part2(&input)
-    );
+use crate::{parse_lines, rng::Rng, trace, trace::Level, Answers, AocError, LineError, Puzzle};
+
+pub struct Day02;
+
+impl Puzzle for Day02 {
+    const YEAR: u16 = 2023;
+    const DAY: u16 = 2;
+    const TITLE: &'static str = "Cube Conundrum";
+    const QUESTIONS: [&'static str; 2] = [
+        "What is the sum of the IDs of those games?",
+        "What is the sum of the power of these sets?",
+    ];
+
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        input_to_games(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part2(input))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
 }
 
-fn part1(input: &str) -> usize {
-    // Parse input into games
-    let games = input_to_games(input);
+/// Solve both parts of the puzzle for `input`.
+pub fn solve(input: &str) -> Result<Answers, AocError> {
+    Day02::solve(input)
+}
 
+fn part1(games: &[Game]) -> usize {
     // Define the "Threshold" game based on the puzzle definition
     let threshold_game = Game {
         game_num: 0,
@@ -27,25 +46,22 @@ fn part1(input: &str) -> usize {
 
     // Filter all games into a set of possible games and then sum their `game_num`s
     games
-        .into_iter()
+        .iter()
         .filter(|game| is_game_possible(&threshold_game, game))
         .map(|game| game.game_num)
         .sum()
 }
 
-fn part2(input: &str) -> usize {
-    // Parse input into games
-    let games = input_to_games(input);
-
+fn part2(games: &[Game]) -> usize {
     // Calculate the power for the minimum set of cubes for each game, then sum them
     games
-        .into_iter()
+        .iter()
         .map(|game| game.red * game.green * game.blue)
         .sum()
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     game_num: usize,
     red: usize,
     green: usize,
@@ -58,74 +74,108 @@ fn is_game_possible(threshold_game: &Game, game_to_test: &Game) -> bool {
         && game_to_test.blue <= threshold_game.blue
 }
 
-fn input_to_games(input: &str) -> Vec<Game> {
-    input.lines().map(line_to_game).collect()
+fn input_to_games(input: &str) -> Result<Vec<Game>, AocError> {
+    parse_lines(Day02::DAY, input, line_to_game)
 }
 
-fn line_to_game(line: &str) -> Game {
-    let game_split: Vec<&str> = line.split(": ").collect();
-    if game_split.len() < 2 {
-        unreachable!("Could not find ':' in line: {line}");
-    }
+fn line_to_game(line: &str) -> Result<Game, LineError> {
+    let (game_label, rounds) = line
+        .split_once(": ")
+        .ok_or_else(|| LineError::new(line, line, "could not find ': '"))?;
 
+    let game_num = game_label.split(' ').next_back().unwrap_or(game_label);
     let mut game = Game {
-        game_num: game_split[0]
-            .split(' ')
-            .last()
-            .unwrap_or_else(|| panic!("Unexpected format of Game number for line {line}"))
+        game_num: game_num
             .parse()
-            .unwrap_or_else(|_| panic!("Could not find game number in {}", game_split[0])),
+            .map_err(|_| LineError::new(line, game_num, "could not parse game number"))?,
         red: 0,
         green: 0,
         blue: 0,
     };
-    for round in game_split[1].split("; ") {
+    for round in rounds.split("; ") {
         for color in round.split(", ") {
-            let color_split: Vec<&str> = color.split(' ').collect();
-            let num_cubes = color_split[0]
+            let (num_cubes, color_name) = color
+                .split_once(' ')
+                .ok_or_else(|| LineError::new(line, color, "expected '<number> <color>'"))?;
+            let num_cubes = num_cubes
                 .parse::<usize>()
-                .unwrap_or_else(|_| panic!("Coudn't find number in {:?}", color));
-            match color_split[1] {
+                .map_err(|_| LineError::new(line, num_cubes, "could not parse number of cubes"))?;
+            match color_name {
                 "red" => game.red = std::cmp::max(game.red, num_cubes),
                 "green" => game.green = std::cmp::max(game.green, num_cubes),
                 "blue" => game.blue = std::cmp::max(game.blue, num_cubes),
-                _ => unreachable!("Unexpected color found in round {round}"),
+                _ => return Err(LineError::new(line, color_name, "unexpected color")),
             }
         }
     }
-    // println!("Parsed line {line} into game: {:?}", game);
-    game
+    trace!(
+        Day02::DAY,
+        Level::Debug,
+        "Parsed line {line} into game: {game:?}"
+    );
+    Ok(game)
+}
+
+/// A game per line, each with up to six rounds of up to 20 cubes of each color.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for game in 1..=size {
+        let rounds: Vec<String> = (0..rng.range(1..=6))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                rng.shuffle(&mut colors);
+                let shown = rng.range(1..=colors.len());
+                colors[..shown]
+                    .iter()
+                    .map(|color| format!("{} {color}", rng.range(1..=20)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        input += &format!("Game {game}: {}\n", rounds.join("; "));
+    }
+    input
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day02::{part1, part2};
+    use crate::day02::{generate, input_to_games, part1, part2, Day02};
+    use crate::fixtures::examples;
+    use crate::rng::Rng;
+    use crate::{AocError, Puzzle};
 
     #[test]
     fn test_part1_sample_input() {
-        assert_eq!(
-            part1(
-                "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-            Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-            Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-            Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-            Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
-            ),
-            8
-        )
+        for (input, answer) in examples(Day02::YEAR, Day02::DAY, 1) {
+            assert_eq!(answer, part1(&input_to_games(&input).unwrap()));
+        }
     }
 
     #[test]
     fn test_part2_sample_input() {
-        assert_eq!(
-            part2(
-                "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-            Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-            Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-            Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-            Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
-            ),
-            2286
-        )
+        for (input, answer) in examples(Day02::YEAR, Day02::DAY, 2) {
+            assert_eq!(answer, part2(&input_to_games(&input).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_input_to_games_unexpected_color() {
+        assert!(matches!(
+            input_to_games("Game 1: 3 blue, 4 red\nGame 2: 1 blue, 2 purple"),
+            Err(AocError::Malformed {
+                day: 2,
+                line: 2,
+                column: 19,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 50);
+        assert_eq!(input, generate(&mut Rng::new(1), 50));
+        assert_eq!(50, input_to_games(&input).unwrap().len());
+        Day02::solve(&input).unwrap();
     }
 }
@@ -1,231 +1,523 @@
-use std::cmp::{max, min};
-
-use crate::parse_file_input;
-
-pub fn run() {
-    let input = parse_file_input(3);
-    println!("Running Day 3");
-    println!(
-        "Part 1 :: What is the sum of all of the part numbers in the engine schematic? --> {:?}",
-        part1(&input)
-    );
-    println!(
-        "Part 2 :: What is the sum of all of the gear ratios in your engine schematic? --> {:?}",
-        part2(&input)
-    );
-}
-
-fn part1(input: &str) -> usize {
-    let schematic = input_to_schematic(input);
-
-    // Iterate over each row and sum each number that has an adjacent symbol
-    let mut sum_of_part_numbers = 0;
-    for (i, row) in schematic.iter().enumerate() {
-        let mut row_sum = 0;
-        let mut number_start_ind = None;
-        // Iterate over each character, keeping track of numbers using number_start_ind
-        for (j, c) in row.iter().enumerate() {
-            if c.is_ascii_digit() {
-                if number_start_ind.is_none() {
-                    number_start_ind = Some(j);
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use crate::{
+    grid::{Grid, Pos, RaggedRow},
+    rng::Rng,
+    Answers, AocError, Puzzle,
+};
+
+pub struct Day03;
+
+impl Puzzle for Day03 {
+    const YEAR: u16 = 2023;
+    const DAY: u16 = 3;
+    const TITLE: &'static str = "Gear Ratios";
+    const QUESTIONS: [&'static str; 2] = [
+        "What is the sum of all of the part numbers in the engine schematic?",
+        "What is the sum of all of the gear ratios in your engine schematic?",
+    ];
+
+    type Input = PartGraph;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        input_to_part_graph(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part2(input))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn render(
+        input: &Self::Input,
+        rows: RangeInclusive<usize>,
+        cols: RangeInclusive<usize>,
+    ) -> Option<String> {
+        Some(input.render(rows, cols))
+    }
+}
+
+/// Solve both parts of the puzzle for `input`.
+pub fn solve(input: &str) -> Result<Answers, AocError> {
+    Day03::solve(input)
+}
+
+fn part1(graph: &PartGraph) -> usize {
+    graph.part_numbers().map(|number| number.value).sum()
+}
+
+fn part2(graph: &PartGraph) -> usize {
+    graph.sum_of_gear_ratios(&GearRule::puzzle())
+}
+
+pub type Schematic = Grid<char>;
+
+/// A number in the schematic, which is a part number if it touches a symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartNumber {
+    pub value: usize,
+    pub row: usize,
+    /// The columns of its digits
+    pub col_span: RangeInclusive<usize>,
+}
+
+/// Any character in the schematic other than a digit or a '.'.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub char: char,
+    pub pos: Pos,
+}
+
+/// The numbers and symbols of a schematic, and which numbers touch which symbols, including
+/// diagonally. Numbers and symbols are referred to by their index, and are in reading order.
+#[derive(Debug)]
+pub struct PartGraph {
+    schematic: Schematic,
+    numbers: Vec<PartNumber>,
+    symbols: Vec<Symbol>,
+    // The symbols each number touches, and the numbers each symbol touches
+    number_symbols: Vec<Vec<usize>>,
+    symbol_numbers: Vec<Vec<usize>>,
+}
+
+impl PartGraph {
+    /// Find every number and symbol in one pass over the schematic, then connect each number to
+    /// the symbols around it.
+    pub fn new(schematic: Schematic) -> Result<PartGraph, AocError> {
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+        for (row, cells) in schematic.rows().enumerate() {
+            let mut number_start = None;
+            // The '.' past the end of the row ends a number at the edge like any other
+            for (col, &c) in cells.iter().chain(&['.']).enumerate() {
+                match (c.is_ascii_digit(), number_start) {
+                    (true, None) => number_start = Some(col),
+                    (false, Some(start)) => {
+                        let digits = cells[start..col].iter().collect();
+                        numbers.push(PartNumber {
+                            value: parse_number(digits, row, start)?,
+                            row,
+                            col_span: start..=col - 1,
+                        });
+                        number_start = None;
+                    }
+                    _ => {}
                 }
-            } else if let Some(start_index) = number_start_ind {
-                if has_adjacent_symbol(&schematic, i, start_index, j - 1) {
-                    row_sum += parse_number_from_point_range(&schematic, i, start_index, j - 1);
+                if !c.is_ascii_digit() && c != '.' {
+                    symbols.push(Symbol {
+                        char: c,
+                        pos: (row, col),
+                    });
                 }
-                number_start_ind = None;
             }
         }
-        // Handle numbers that end at the end of the row
-        if let Some(start_index) = number_start_ind {
-            if has_adjacent_symbol(&schematic, i, start_index, row.len() - 1) {
-                row_sum += parse_number_from_point_range(&schematic, i, start_index, row.len() - 1);
+
+        let symbol_at: HashMap<Pos, usize> = symbols
+            .iter()
+            .enumerate()
+            .map(|(i, symbol)| (symbol.pos, i))
+            .collect();
+        let mut number_symbols = vec![Vec::new(); numbers.len()];
+        let mut symbol_numbers = vec![Vec::new(); symbols.len()];
+        for (i, number) in numbers.iter().enumerate() {
+            let around = schematic.region(
+                number.row.saturating_sub(1)..=number.row + 1,
+                number.col_span.start().saturating_sub(1)..=number.col_span.end() + 1,
+            );
+            for (pos, _) in around {
+                if let Some(&symbol) = symbol_at.get(&pos) {
+                    number_symbols[i].push(symbol);
+                    symbol_numbers[symbol].push(i);
+                }
             }
         }
-        sum_of_part_numbers += row_sum;
+
+        Ok(PartGraph {
+            schematic,
+            numbers,
+            symbols,
+            number_symbols,
+            symbol_numbers,
+        })
     }
 
-    sum_of_part_numbers
-}
+    pub fn numbers(&self) -> &[PartNumber] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// The numbers that touch at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &PartNumber> {
+        self.numbers
+            .iter()
+            .zip(&self.number_symbols)
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
+
+    /// The indices of the symbols touching the number at index `number`.
+    pub fn symbols_touching(&self, number: usize) -> &[usize] {
+        &self.number_symbols[number]
+    }
+
+    /// The indices of the numbers touching the symbol at index `symbol`.
+    pub fn numbers_touching(&self, symbol: usize) -> &[usize] {
+        &self.symbol_numbers[symbol]
+    }
+
+    /// The indices of the symbols touching exactly `count` numbers.
+    pub fn symbols_with_parts(&self, count: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.symbols.len()).filter(move |&symbol| self.symbol_numbers[symbol].len() == count)
+    }
+
+    /// The ratio of each gear under `rule`, in reading order.
+    pub fn gear_ratios<'a>(&'a self, rule: &'a GearRule) -> impl Iterator<Item = usize> + 'a {
+        self.symbols_with_parts(rule.parts)
+            .filter(|&symbol| rule.is_gear(&self.symbols[symbol]))
+            .map(|symbol| {
+                let values = self.symbol_numbers[symbol]
+                    .iter()
+                    .map(|&number| self.numbers[number].value);
+                rule.combine.apply(values)
+            })
+    }
+
+    pub fn sum_of_gear_ratios(&self, rule: &GearRule) -> usize {
+        self.gear_ratios(rule).sum()
+    }
 
-fn part2(input: &str) -> usize {
-    let schematic = input_to_schematic(input);
+    /// The schematic in `rows` and `cols`, each row after its line number, with part numbers in
+    /// green, other numbers in red, and the gears of the puzzle's rule and their parts in
+    /// yellow. Ends with a key to the colours.
+    pub fn render(&self, rows: RangeInclusive<usize>, cols: RangeInclusive<usize>) -> String {
+        let mut colours = Grid::new(self.schematic.width(), self.schematic.height(), None);
+        for (number, symbols) in self.numbers.iter().zip(&self.number_symbols) {
+            let colour = match symbols.is_empty() {
+                true => RED,
+                false => GREEN,
+            };
+            for col in number.col_span.clone() {
+                colours[(number.row, col)] = Some(colour);
+            }
+        }
+        let rule = GearRule::puzzle();
+        for symbol in self.symbols_with_parts(rule.parts) {
+            if !rule.is_gear(&self.symbols[symbol]) {
+                continue;
+            }
+            colours[self.symbols[symbol].pos] = Some(YELLOW);
+            for &number in &self.symbol_numbers[symbol] {
+                let number = &self.numbers[number];
+                for col in number.col_span.clone() {
+                    colours[(number.row, col)] = Some(YELLOW);
+                }
+            }
+        }
 
-    // Find each "*" and get all adjacent numbers, adding the gear ratio the total if
-    // there are exactly 2 adjacent numbers.
-    let mut sum_of_gear_ratios = 0;
-    for (i, row) in schematic.iter().enumerate() {
-        for (j, c) in row.iter().enumerate() {
-            if *c == '*' {
-                let numbers = get_numbers_from_points(
-                    &schematic,
-                    &get_adjacent_char_indicies(&schematic, |c| c.is_ascii_digit(), i, j, j),
-                );
-                if numbers.len() == 2 {
-                    sum_of_gear_ratios += numbers[0] * numbers[1];
+        let rows = *rows.start()..rows.end().saturating_add(1).min(self.schematic.height());
+        let width = rows.end.to_string().len();
+        let mut rendered = String::new();
+        for row in rows {
+            rendered += &format!("{:>width$} ", row + 1);
+            for (pos, cell) in self.schematic.region(row..=row, cols.clone()) {
+                match colours[pos] {
+                    Some(colour) => rendered += &format!("{colour}{cell}{RESET}"),
+                    None => rendered.push(*cell),
                 }
             }
+            rendered.push('\n');
         }
+        rendered += &format!(
+            "{GREEN}part number{RESET}, {RED}not a part number{RESET}, {YELLOW}gear and its parts{RESET}\n"
+        );
+        rendered
     }
 
-    sum_of_gear_ratios
+    /// The index of the symbol touching the most numbers, or the first of them if there's a tie.
+    pub fn busiest_symbol(&self) -> Option<usize> {
+        (0..self.symbols.len()).reduce(|busiest, symbol| {
+            match self.symbol_numbers[symbol].len() > self.symbol_numbers[busiest].len() {
+                true => symbol,
+                false => busiest,
+            }
+        })
+    }
 }
 
-type Schematic = Vec<Vec<char>>;
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
 
-fn input_to_schematic(input: &str) -> Schematic {
-    input.lines().map(|l| l.chars().collect()).collect()
+/// How the numbers touching a gear are combined into its ratio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combine {
+    Product,
+    Sum,
+    /// The largest of them, or 0 for no numbers
+    Max,
+    /// 1 for every gear whatever the numbers, so summing ratios counts the gears
+    Count,
 }
 
-fn has_adjacent_symbol(
-    schematic: &Schematic,
-    row_index: usize,
-    col_index_start: usize,
-    col_index_end: usize,
-) -> bool {
-    !get_adjacent_char_indicies(
-        schematic,
-        |c| !c.is_ascii_digit() && c != '.',
-        row_index,
-        col_index_start,
-        col_index_end,
-    )
-    .is_empty()
+impl Combine {
+    fn apply(self, values: impl Iterator<Item = usize>) -> usize {
+        match self {
+            Combine::Product => values.product(),
+            Combine::Sum => values.sum(),
+            Combine::Max => values.max().unwrap_or(0),
+            Combine::Count => 1,
+        }
+    }
 }
 
-fn get_adjacent_char_indicies<F>(
-    schematic: &Schematic,
-    char_match: F,
-    row_index: usize,
-    col_index_start: usize,
-    col_index_end: usize,
-) -> Vec<(usize, usize)>
-where
-    F: Fn(char) -> bool,
-{
-    let mut char_indicies: Vec<(usize, usize)> = vec![];
-    // Col range from 1 left of the start and 1 right of the end
-    let col_range = max(0, col_index_start as isize - 1) as usize
-        ..=min(schematic[0].len() - 1, col_index_end + 1);
-
-    // Row above
-    if row_index > 0 {
-        for j in col_range.clone() {
-            if char_match(schematic[row_index - 1][j]) {
-                char_indicies.push((row_index - 1, j));
-            }
+/// Which symbols are gears and how their ratios are worked out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    /// The symbols that can be gears, or every symbol if empty
+    pub symbols: Vec<char>,
+    /// How many numbers a gear touches
+    pub parts: usize,
+    pub combine: Combine,
+}
+
+impl GearRule {
+    pub fn new(symbols: &str, parts: usize, combine: Combine) -> GearRule {
+        GearRule {
+            symbols: symbols.chars().collect(),
+            parts,
+            combine,
         }
     }
 
-    // Same row
-    if col_index_start > 0 && char_match(schematic[row_index][col_index_start - 1]) {
-        char_indicies.push((row_index, col_index_start - 1));
-    }
-    if col_index_end < schematic[0].len() - 1 && char_match(schematic[row_index][col_index_end + 1])
-    {
-        char_indicies.push((row_index, col_index_end + 1));
+    /// The puzzle's rule: a '*' touching exactly two part numbers, whose ratio is their product.
+    pub fn puzzle() -> GearRule {
+        GearRule::new("*", 2, Combine::Product)
     }
 
-    // Row below
-    if row_index < schematic[0].len() - 1 {
-        for j in col_range {
-            if char_match(schematic[row_index + 1][j]) {
-                char_indicies.push((row_index + 1, j));
-            }
-        }
+    fn is_gear(&self, symbol: &Symbol) -> bool {
+        self.symbols.is_empty() || self.symbols.contains(&symbol.char)
     }
+}
+
+fn input_to_part_graph(input: &str) -> Result<PartGraph, AocError> {
+    PartGraph::new(input_to_schematic(input)?)
+}
 
-    char_indicies
+// The schematic can be any size, but every line must be as long as the first
+fn input_to_schematic(input: &str) -> Result<Schematic, AocError> {
+    Grid::from_rows(input.lines().map(|line| line.chars().collect())).map_err(
+        |RaggedRow { row, len, width }| {
+            // Point at the characters past the width of the first line, or the whole short line
+            let line = input.lines().nth(row).unwrap_or_default();
+            let (column, text) = match len > width {
+                true => (width + 1, line.chars().skip(width).collect()),
+                false => (1, line.to_string()),
+            };
+            AocError::Malformed {
+                day: Day03::DAY,
+                line: row + 1,
+                column,
+                text,
+                reason: format!("expected {width} characters like the first line, found {len}"),
+            }
+        },
+    )
 }
 
-fn parse_number_from_point_range(
-    schematic: &Schematic,
+fn parse_number(
+    number_string: String,
     row_index: usize,
     col_index_start: usize,
-    col_index_end: usize,
-) -> usize {
-    schematic[row_index][col_index_start..=col_index_end]
-        .iter()
-        .collect::<String>()
-        .parse::<usize>()
-        .expect("Failed to parse number")
-}
-
-fn parse_number_from_point(schematic: &Schematic, row_index: usize, col_index: usize) -> usize {
-    let mut number_string = String::from(schematic[row_index][col_index]);
-
-    if col_index > 0 {
-        for j in (0..col_index).rev() {
-            if schematic[row_index][j].is_ascii_digit() {
-                number_string.insert_str(0, &schematic[row_index][j].to_string());
-            } else {
-                break;
-            }
-        }
-    }
+) -> Result<usize, AocError> {
+    number_string.parse().map_err(|e| AocError::Malformed {
+        day: Day03::DAY,
+        line: row_index + 1,
+        column: col_index_start + 1,
+        reason: format!("could not parse number: {e}"),
+        text: number_string,
+    })
+}
 
-    if col_index < schematic[row_index].len() - 1 {
-        for j in col_index + 1..schematic[row_index].len() {
-            if schematic[row_index][j].is_ascii_digit() {
-                number_string.push(schematic[row_index][j]);
-            } else {
-                break;
+/// A square schematic `size` characters wide, with numbers of up to three digits and symbols
+/// scattered among the dots.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '=', '%', '-', '&'];
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut row = String::with_capacity(size);
+        while row.len() < size {
+            match rng.below(10) {
+                0 | 1 => {
+                    let digits = rng.range(1..=3.min(size - row.len()));
+                    row.push(char::from(b'1' + rng.below(9) as u8));
+                    for _ in 1..digits {
+                        row.push(char::from(b'0' + rng.below(10) as u8));
+                    }
+                    // Keep the next number from running into this one
+                    if row.len() < size {
+                        row.push('.');
+                    }
+                }
+                2 => row.push(*rng.choose(&SYMBOLS)),
+                _ => row.push('.'),
             }
         }
+        input += &row;
+        input.push('\n');
     }
-
-    number_string
-        .parse()
-        .unwrap_or_else(|_| panic!("Unable to parse number from {number_string}"))
+    input
 }
 
-fn get_numbers_from_points(schematic: &Schematic, points: &[(usize, usize)]) -> Vec<usize> {
-    let mut numbers: Vec<usize> = vec![];
+#[cfg(test)]
+mod tests {
+    use crate::day03::{
+        generate, input_to_part_graph, input_to_schematic, part1, part2, Combine, Day03, GearRule,
+        PartNumber, Symbol, GREEN, RED, RESET, YELLOW,
+    };
+    use crate::fixtures::examples;
+    use crate::rng::Rng;
+    use crate::{AocError, Puzzle};
 
-    if !points.is_empty() {
-        let mut cur_point = points[0];
-        numbers.push(parse_number_from_point(schematic, cur_point.0, cur_point.1));
+    #[test]
+    fn test_part_graph() {
+        let (input, _) = &examples(Day03::YEAR, Day03::DAY, 1)[0];
+        let graph = input_to_part_graph(input).unwrap();
+        assert_eq!(
+            vec![467, 114, 35, 633, 617, 58, 592, 755, 664, 598],
+            graph.numbers().iter().map(|n| n.value).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            PartNumber {
+                value: 664,
+                row: 9,
+                col_span: 1..=3
+            },
+            graph.numbers()[8]
+        );
+        assert_eq!(
+            Symbol {
+                char: '*',
+                pos: (1, 3)
+            },
+            graph.symbols()[0]
+        );
+        assert_eq!(6, graph.symbols().len());
 
-        for (i, p) in points.iter().enumerate() {
-            if i == 0 {
-                continue;
-            } else if cur_point.0 != p.0 || p.1 as isize - cur_point.1 as isize != 1 {
-                numbers.push(parse_number_from_point(schematic, p.0, p.1));
-            }
-            cur_point = *p;
-        }
+        // 467 and 35 share the first '*', while 114 and 58 touch nothing
+        assert_eq!(&[0, 2], graph.numbers_touching(0));
+        assert_eq!(&[0], graph.symbols_touching(2));
+        assert!(graph.symbols_touching(1).is_empty());
+        assert_eq!(8, graph.part_numbers().count());
+        assert_eq!(vec![0, 5], graph.symbols_with_parts(2).collect::<Vec<_>>());
+        assert_eq!(Some(0), graph.busiest_symbol());
+        assert_eq!(None, input_to_part_graph("...").unwrap().busiest_symbol());
     }
 
-    numbers
-}
+    #[test]
+    fn test_gear_rules() {
+        let (input, _) = &examples(Day03::YEAR, Day03::DAY, 2)[0];
+        let graph = input_to_part_graph(input).unwrap();
+        assert_eq!(
+            vec![16345, 451490],
+            graph.gear_ratios(&GearRule::puzzle()).collect::<Vec<_>>()
+        );
+        let sum_rule = GearRule::new("*", 2, Combine::Sum);
+        assert_eq!(467 + 35 + 755 + 598, graph.sum_of_gear_ratios(&sum_rule));
+        let max_rule = GearRule::new("*", 2, Combine::Max);
+        assert_eq!(467 + 755, graph.sum_of_gear_ratios(&max_rule));
+        // Every symbol but the two gears touches a single number
+        let single_rule = GearRule::new("", 1, Combine::Sum);
+        assert_eq!(
+            633 + 617 + 592 + 664,
+            graph.sum_of_gear_ratios(&single_rule)
+        );
 
-#[cfg(test)]
-mod tests {
-    use crate::day03::{input_to_schematic, parse_number_from_point_range, part1, part2};
-    const SAMPLE_INPUT: &str = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
+        let graph = input_to_part_graph("4.5..\n.#..$\n6....").unwrap();
+        // A '#' touching exactly three parts
+        assert_eq!(
+            120,
+            graph.sum_of_gear_ratios(&GearRule::new("#", 3, Combine::Product))
+        );
+        assert_eq!(
+            0,
+            graph.sum_of_gear_ratios(&GearRule::new("*", 3, Combine::Product))
+        );
+        // The symbols touching no parts
+        let lonely_rule = GearRule::new("", 0, Combine::Count);
+        assert_eq!(1, graph.sum_of_gear_ratios(&lonely_rule));
+        let all_rule = GearRule::new("", 3, Combine::Count);
+        assert_eq!(1, graph.sum_of_gear_ratios(&all_rule));
+        assert_eq!(
+            vec![0],
+            graph
+                .gear_ratios(&GearRule::new("", 0, Combine::Max))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        // The '*' touches three numbers, so it isn't a gear
+        let graph = input_to_part_graph("4.5..\n.*...\n6..78").unwrap();
+        let rendered = graph.render(0..=usize::MAX, 0..=usize::MAX);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(4, lines.len());
+        assert_eq!(format!("1 {GREEN}4{RESET}.{GREEN}5{RESET}.."), lines[0]);
+        assert_eq!(
+            format!("3 {GREEN}6{RESET}..{RED}7{RESET}{RED}8{RESET}"),
+            lines[2]
+        );
+
+        // The gear and both of its parts are yellow
+        let graph = input_to_part_graph("4.5..\n.*...").unwrap();
+        let rendered = graph.render(0..=0, 0..=2);
+        assert_eq!(
+            format!("1 {YELLOW}4{RESET}.{YELLOW}5{RESET}"),
+            rendered.lines().next().unwrap()
+        );
+        assert_eq!(2, rendered.lines().count());
+
+        // Cropping past the edges leaves those rows and columns out
+        let graph = input_to_part_graph(&"....\n".repeat(12)).unwrap();
+        assert_eq!(
+            vec!["11 ..", "12 .."],
+            graph
+                .render(10..=20, 2..=9)
+                .lines()
+                .take(2)
+                .collect::<Vec<_>>()
+        );
+    }
 
     #[test]
-    fn test_parse_number_from_point_range() {
-        let schematic = input_to_schematic(SAMPLE_INPUT);
-        assert_eq!(467, parse_number_from_point_range(&schematic, 0, 0, 2));
-        assert_eq!(114, parse_number_from_point_range(&schematic, 0, 5, 7));
-        assert_eq!(617, parse_number_from_point_range(&schematic, 4, 0, 2));
-        assert_eq!(664, parse_number_from_point_range(&schematic, 9, 1, 3));
-        assert_eq!(598, parse_number_from_point_range(&schematic, 9, 5, 7));
+    fn test_part_graph_number_too_large() {
+        assert!(matches!(
+            input_to_part_graph("..99999999999999999999999*"),
+            Err(AocError::Malformed {
+                day: 3,
+                line: 1,
+                column: 3,
+                ..
+            })
+        ));
     }
 
     #[test]
     fn test_part1_sample_input() {
-        assert_eq!(4361, part1(SAMPLE_INPUT))
+        for (input, answer) in examples(Day03::YEAR, Day03::DAY, 1) {
+            assert_eq!(answer, part1(&input_to_part_graph(&input).unwrap()));
+        }
     }
 
     #[test]
@@ -233,7 +525,8 @@ mod tests {
         assert_eq!(
             0,
             part1(
-                "467..114..
+                &input_to_part_graph(
+                    "467..114..
 ..........
 ..35..633.
 ..........
@@ -243,12 +536,70 @@ mod tests {
 ......755.
 ..........
 .664.598.."
+                )
+                .unwrap()
             )
         )
     }
 
+    #[test]
+    fn test_non_square_schematics() {
+        // Wider than it is tall, with a symbol on the last row
+        let input = "467..114..\n...*......";
+        let schematic = input_to_schematic(input).unwrap();
+        assert_eq!((10, 2), (schematic.width(), schematic.height()));
+        assert_eq!(467, part1(&input_to_part_graph(input).unwrap()));
+
+        // Taller than it is wide, with numbers above and below the gear
+        let input = "1\n*\n2\n.\n3";
+        let schematic = input_to_schematic(input).unwrap();
+        assert_eq!((1, 5), (schematic.width(), schematic.height()));
+        let graph = input_to_part_graph(input).unwrap();
+        assert_eq!(3, part1(&graph));
+        assert_eq!(2, part2(&graph));
+
+        assert_eq!(0, part1(&input_to_part_graph("").unwrap()));
+    }
+
+    #[test]
+    fn test_input_to_schematic_ragged_lines() {
+        assert!(matches!(
+            input_to_schematic("467..\n...*\n..35."),
+            Err(AocError::Malformed {
+                day: 3,
+                line: 2,
+                column: 1,
+                ..
+            })
+        ));
+        match input_to_schematic("467..\n.....\n...*..#") {
+            Err(AocError::Malformed {
+                line,
+                column,
+                text,
+                reason,
+                ..
+            }) => {
+                assert_eq!((3, 6, String::from(".#")), (line, column, text));
+                assert_eq!("expected 5 characters like the first line, found 7", reason);
+            }
+            other => panic!("expected a malformed line, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_part2_sample_input() {
-        assert_eq!(467835, part2(SAMPLE_INPUT));
+        for (input, answer) in examples(Day03::YEAR, Day03::DAY, 2) {
+            assert_eq!(answer, part2(&input_to_part_graph(&input).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 40);
+        assert_eq!(input, generate(&mut Rng::new(1), 40));
+        assert!(input.lines().all(|line| line.len() == 40));
+        assert_eq!(40, input.lines().count());
+        Day03::solve(&input).unwrap();
     }
 }
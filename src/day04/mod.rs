@@ -1,39 +1,58 @@
 use std::collections::HashSet;
 
-use crate::parse_file_input;
-
-pub fn run() {
-    let input = parse_file_input(4);
-    println!("Running Day 4");
-    println!(
-        "Part 1 :: How many points are they worth in total? --> {:?}",
-        part1(&input)
-    );
-    println!(
-        "Part 2 :: How many total scratchcards do you end up with? --> {:?}",
-        part2(&input)
-    );
+use crate::{parse_lines, rng::Rng, Answers, AocError, LineError, Puzzle};
+
+pub struct Day04;
+
+impl Puzzle for Day04 {
+    const YEAR: u16 = 2023;
+    const DAY: u16 = 4;
+    const TITLE: &'static str = "Scratchcards";
+    const QUESTIONS: [&'static str; 2] = [
+        "How many points are they worth in total?",
+        "How many total scratchcards do you end up with?",
+    ];
+
+    type Input = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        input_to_cards(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part2(input))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+/// Solve both parts of the puzzle for `input`.
+pub fn solve(input: &str) -> Result<Answers, AocError> {
+    Day04::solve(input)
 }
 
-fn part1(input: &str) -> usize {
-    // Parse input into `Card`s, get each card value and sum
-    input_to_cards(input)
-        .iter()
-        .fold(0, |sum, card| sum + card.value())
+fn part1(cards: &[Card]) -> usize {
+    // Get each card value and sum
+    cards.iter().fold(0, |sum, card| sum + card.value())
 }
 
-fn part2(input: &str) -> usize {
-    let cards = input_to_cards(input);
+fn part2(cards: &[Card]) -> usize {
     // Start with a card count of 1 for each
     let mut card_counts = vec![1; cards.len()];
 
     // For each card get the number of winning matches and increment subsequent card
     // counts based on the number of winning matches. Do that step once for every version
-    // of the card, tracked by `card_counts`.
+    // of the card, tracked by `card_counts`. Cards past the end of the table aren't won.
     for (i, card) in cards.iter().enumerate() {
         let card_num_matches = card.winning_number_matches().len();
         for _ in 0..card_counts[i] {
-            for j in 1..=card_num_matches {
+            for j in 1..=card_num_matches.min(cards.len() - 1 - i) {
                 card_counts[i + j] += 1;
             }
         }
@@ -43,7 +62,7 @@ fn part2(input: &str) -> usize {
 }
 
 #[derive(Debug)]
-struct Card {
+pub struct Card {
     winning_numbers: Vec<usize>,
     numbers_card_has: Vec<usize>,
 }
@@ -69,82 +88,135 @@ impl Card {
     }
 }
 
-fn input_to_cards(input: &str) -> Vec<Card> {
-    input
-        .lines()
-        .map(|line| {
-            line.split(": ").last().unwrap().split(" | ").fold(
-                Card {
-                    winning_numbers: vec![],
-                    numbers_card_has: vec![],
-                },
-                |mut card, part| {
-                    if card.winning_numbers.is_empty() {
-                        card.winning_numbers = numbers_string_to_number_vec(part);
-                    } else {
-                        card.numbers_card_has = numbers_string_to_number_vec(part);
-                    }
-                    card
-                },
-            )
+fn input_to_cards(input: &str) -> Result<Vec<Card>, AocError> {
+    parse_lines(Day04::DAY, input, |line| {
+        let (_, numbers) = line
+            .split_once(": ")
+            .ok_or_else(|| LineError::new(line, line, "could not find ': '"))?;
+        let (winning_numbers, numbers_card_has) = numbers
+            .split_once(" | ")
+            .ok_or_else(|| LineError::new(line, numbers, "could not find ' | '"))?;
+        Ok(Card {
+            winning_numbers: numbers_string_to_number_vec(winning_numbers)
+                .map_err(|e| e.within(line, winning_numbers))?,
+            numbers_card_has: numbers_string_to_number_vec(numbers_card_has)
+                .map_err(|e| e.within(line, numbers_card_has))?,
         })
-        .collect()
+    })
 }
 
-fn numbers_string_to_number_vec(numbers: &str) -> Vec<usize> {
+fn numbers_string_to_number_vec(numbers: &str) -> Result<Vec<usize>, LineError> {
     numbers
         .split_whitespace()
         .map(|num| {
-            num.trim()
-                .parse::<usize>()
-                .unwrap_or_else(|e| panic!("Couldn't parse number from '{num}' : {e}"))
+            num.parse::<usize>()
+                .map_err(|e| LineError::new(numbers, num, format!("couldn't parse number: {e}")))
         })
         .collect()
 }
 
+/// A card per line with ten winning numbers and twenty five numbers the card has, never winning
+/// more copies than there are cards after it. Copies win copies, so cards win fewer as the total
+/// nears a limit that keeps part 2 quick, like the few million of the puzzle's inputs.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const MAX_TOTAL_CARDS: usize = 10_000_000;
+    let format = |numbers: &[usize]| {
+        numbers
+            .iter()
+            .map(|n| format!("{n:>2}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let mut input = String::new();
+    let mut card_counts = vec![1; size];
+    let mut total_cards = size;
+    for card in 1..=size {
+        let mut numbers: Vec<usize> = (1..100).collect();
+        rng.shuffle(&mut numbers);
+        let (winning, others) = numbers.split_at(10);
+        let count = card_counts[card - 1];
+        let most = (MAX_TOTAL_CARDS.saturating_sub(total_cards) / count)
+            .min(winning.len())
+            .min(size - card);
+        let matches = rng.range(0..=most);
+        for next_count in &mut card_counts[card..card + matches] {
+            *next_count += count;
+        }
+        total_cards += count * matches;
+        let mut numbers_card_has: Vec<usize> = winning[..matches]
+            .iter()
+            .chain(&others[..25 - matches])
+            .copied()
+            .collect();
+        rng.shuffle(&mut numbers_card_has);
+        input += &format!(
+            "Card {card:>3}: {} | {}\n",
+            format(winning),
+            format(&numbers_card_has)
+        );
+    }
+    input
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::day04::{numbers_string_to_number_vec, part1, part2};
+    use crate::day04::{
+        generate, input_to_cards, numbers_string_to_number_vec, part1, part2, Day04,
+    };
+    use crate::fixtures::examples;
+    use crate::rng::Rng;
+    use crate::{AocError, Puzzle};
 
     #[test]
     fn test_numbers_string_to_number_vec() {
         assert_eq!(
             vec![1, 2, 3, 4],
-            numbers_string_to_number_vec("  1     2 3     4")
+            numbers_string_to_number_vec("  1     2 3     4").unwrap()
         );
         assert_eq!(
             vec![9, 31, 6, 82, 29, 41, 67, 75, 63, 10, 9],
-            numbers_string_to_number_vec(" 9 31  6 82 29 41 67 75 63 10  9")
+            numbers_string_to_number_vec(" 9 31  6 82 29 41 67 75 63 10  9").unwrap()
         );
     }
 
     #[test]
     fn test_part1_sample_input() {
-        assert_eq!(
-            13,
-            part1(
-                "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
-            )
-        )
+        for (input, answer) in examples(Day04::YEAR, Day04::DAY, 1) {
+            assert_eq!(answer, part1(&input_to_cards(&input).unwrap()));
+        }
     }
 
     #[test]
     fn test_part2_sample_input() {
-        assert_eq!(
-            30,
-            part2(
-                "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-                Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-                Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-                Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-                Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-                Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
-            )
-        )
+        for (input, answer) in examples(Day04::YEAR, Day04::DAY, 2) {
+            assert_eq!(answer, part2(&input_to_cards(&input).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_part2_last_card_wins() {
+        let cards = input_to_cards("Card 1: 1 2 | 1 3\nCard 2: 4 5 | 4 5").unwrap();
+        assert_eq!(3, part2(&cards));
+    }
+
+    #[test]
+    fn test_input_to_cards_bad_number() {
+        assert!(matches!(
+            input_to_cards("Card 1: 41 48 | 83 86\nCard 2: 13 32 | 61 3O 68"),
+            Err(AocError::Malformed {
+                day: 4,
+                line: 2,
+                column: 20,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 50);
+        assert_eq!(input, generate(&mut Rng::new(1), 50));
+        assert_eq!(50, input_to_cards(&input).unwrap().len());
+        Day04::solve(&input).unwrap();
     }
 }
@@ -1,36 +1,57 @@
 use std::cmp::{max, min};
 
-use crate::parse_file_input;
-
-pub fn run() {
-    let input = parse_file_input(5);
-    println!("Running Day 5");
-    println!(
-        "Part 1 :: What is the lowest location number that corresponds to any of the initial seed numbers? --> {:?}",
-        part1(&input)
-    );
-    println!(
-        "Part 2 :: What is the lowest location number that corresponds to any of the initial seed numbers? --> {:?}",
-        part2(&input)
-    );
+use crate::{rng::Rng, Answers, AocError, LineError, Puzzle};
+
+pub struct Day05;
+
+impl Puzzle for Day05 {
+    const YEAR: u16 = 2023;
+    const DAY: u16 = 5;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+    const QUESTIONS: [&'static str; 2] = [
+        "What is the lowest location number that corresponds to any of the initial seed numbers?",
+        "What is the lowest location number that corresponds to any of the initial seed numbers?",
+    ];
+
+    type Input = Almanac;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input_to_almanac(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+/// Solve both parts of the puzzle for `input`.
+pub fn solve(input: &str) -> Result<Answers, AocError> {
+    Day05::solve(input)
 }
 
-fn part1(input: &str) -> usize {
-    let (seeds, categories) = parse_input_to_seeds_and_maps(input);
+fn part1(almanac: &Almanac) -> Result<usize, AocError> {
     let mut destinations: Vec<usize> = vec![];
     // Process one seed at a time for all categories, building a destinations set that has
     // all the final locations of the initial seeds.
-    for seed in seeds {
-        let mut dest = seed;
-        for category in categories.iter() {
+    for seed in almanac.seeds.iter() {
+        let mut dest = *seed;
+        for category in almanac.categories.iter() {
             dest = category.get_destination(dest);
         }
         destinations.push(dest);
     }
-    *destinations.iter().min().unwrap()
+    destinations.into_iter().min().ok_or_else(|| no_seeds(1))
 }
 
-fn part2(input: &str) -> usize {
+fn part2(almanac: &Almanac) -> Result<usize, AocError> {
     // Same idea as part1 but use ranges of seeds instead of single seeds.
     // Start by going category-by-category and mapping the ranges.
     // If a range isn't fully contained in a mapping, first map the contained part,
@@ -38,15 +59,25 @@ fn part2(input: &str) -> usize {
     // for the same category.
     // At the end, the 'lowest' location from each range is the only relevant part.
     // The solution is then the lowest range start value of all the seed location ranges.
-    let (seeds, categories) = parse_input_to_seeds_and_maps(input);
 
-    // Construct a vector of ranges in the form (start, end)
+    // Construct a vector of ranges in the form (start, end), leaving out empty ones
     let mut seed_ranges = Vec::new();
-    for seed_range in seeds.chunks_exact(2) {
-        seed_ranges.push((seed_range[0], seed_range[0] + seed_range[1] - 1))
+    for seed_range in almanac.seeds.chunks_exact(2) {
+        let [start, length] = [seed_range[0], seed_range[1]];
+        if length == 0 {
+            continue;
+        }
+        let end = start
+            .checked_add(length - 1)
+            .ok_or_else(|| AocError::MissingAnswer {
+                day: Day05::DAY,
+                part: 2,
+                reason: format!("seed range {start} {length} ends past {}", usize::MAX),
+            })?;
+        seed_ranges.push((start, end))
     }
 
-    for category in categories {
+    for category in almanac.categories.iter() {
         let mut destination_ranges = vec![];
         while let Some(seed_range) = seed_ranges.pop() {
             let mut had_mapping_match = false;
@@ -91,7 +122,25 @@ fn part2(input: &str) -> usize {
 
         seed_ranges = destination_ranges;
     }
-    seed_ranges.iter().map(|s| s.0).min().unwrap()
+    seed_ranges
+        .iter()
+        .map(|s| s.0)
+        .min()
+        .ok_or_else(|| no_seeds(2))
+}
+
+fn no_seeds(part: u8) -> AocError {
+    AocError::MissingAnswer {
+        day: Day05::DAY,
+        part,
+        reason: String::from("there are no seeds"),
+    }
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<usize>,
+    categories: Vec<Category>,
 }
 
 #[derive(Debug)]
@@ -127,23 +176,26 @@ impl Map {
     }
 }
 
-fn parse_input_to_seeds_and_maps(input: &str) -> (Vec<usize>, Vec<Category>) {
-    let mut lines = input.lines();
-    let seeds: Vec<usize> = lines
-        .next()
-        .expect("Failed to parse first line of input")
+fn parse_input_to_almanac(input: &str) -> Result<Almanac, AocError> {
+    let mut lines = input.lines().enumerate();
+    let first_line = lines.next().map_or("", |(_, line)| line);
+    let seeds: Vec<usize> = first_line
         .strip_prefix("seeds: ")
-        .unwrap()
+        .ok_or_else(|| {
+            LineError::new(first_line, first_line, "expected 'seeds: '").at(Day05::DAY, 1)
+        })?
         .split_whitespace()
         .map(|seed| {
-            seed.parse()
-                .unwrap_or_else(|e| panic!("Failed to parse seed num from '{seed}': {e}"))
+            seed.parse().map_err(|e| {
+                LineError::new(first_line, seed, format!("failed to parse seed num: {e}"))
+                    .at(Day05::DAY, 1)
+            })
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     let mut categories: Vec<Category> = vec![];
     let mut cur_category_maps: Vec<Map> = vec![];
-    for line in lines {
+    for (i, line) in lines {
         if line.trim().is_empty() {
             // indicates the end of a category - push the cur_category_maps as a new category
             if !cur_category_maps.is_empty() {
@@ -157,19 +209,7 @@ fn parse_input_to_seeds_and_maps(input: &str) -> (Vec<usize>, Vec<Category>) {
             cur_category_maps.clear();
         } else {
             // it's a map line, parse and add to cur_category_maps
-            let parts: Vec<usize> = line
-                .split_whitespace()
-                .map(|part| {
-                    part.parse().unwrap_or_else(|e| {
-                        panic!("Could not parse number from mapping part: {part}: {e}")
-                    })
-                })
-                .collect();
-            cur_category_maps.push(Map {
-                destination_range_start: parts[0],
-                source_range_start: parts[1],
-                range_length: parts[2],
-            })
+            cur_category_maps.push(line_to_map(line).map_err(|e| e.at(Day05::DAY, i + 1))?)
         }
     }
     // Add the final category
@@ -179,12 +219,82 @@ fn parse_input_to_seeds_and_maps(input: &str) -> (Vec<usize>, Vec<Category>) {
         });
     }
 
-    (seeds, categories)
+    Ok(Almanac { seeds, categories })
+}
+
+fn line_to_map(line: &str) -> Result<Map, LineError> {
+    let parts: Vec<usize> = line
+        .split_whitespace()
+        .map(|part| {
+            part.parse().map_err(|e| {
+                LineError::new(
+                    line,
+                    part,
+                    format!("could not parse number from mapping part: {e}"),
+                )
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    match parts[..] {
+        [destination_range_start, source_range_start, range_length] => Ok(Map {
+            destination_range_start,
+            source_range_start,
+            range_length,
+        }),
+        _ => Err(LineError::new(line, line, "expected exactly 3 numbers")),
+    }
+}
+
+/// Ten seed ranges and a map of `size` ranges (at most 2^31) for each category, with numbers below
+/// 2^32 like the puzzle's. The source ranges of a map don't overlap, but their destinations can.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const CATEGORIES: [&str; 7] = [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ];
+    const LIMIT: usize = 1 << 32;
+    let seeds: Vec<String> = (0..10)
+        .flat_map(|_| {
+            let length = rng.range(1..=100_000_000);
+            [rng.below(LIMIT - length), length]
+        })
+        .map(|n| n.to_string())
+        .collect();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+    // Each map's source range is somewhere in its own slot, so that none overlap. A slot needs
+    // room for a range and a gap, so there can be at most half as many maps as numbers.
+    let size = size.min(LIMIT / 2);
+    let slot = LIMIT / size.max(1);
+    for category in CATEGORIES {
+        let mut maps: Vec<String> = (0..size)
+            .map(|i| {
+                let length = rng.range(1..=slot / 2);
+                let source = i * slot + rng.below(slot - length);
+                let destination = rng.below(LIMIT - length);
+                format!("{destination} {source} {length}")
+            })
+            .collect();
+        rng.shuffle(&mut maps);
+        input += &format!("\n{category} map:\n{}\n", maps.join("\n"));
+    }
+    input
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day05::{part1, part2, Map};
+    use crate::day05::{
+        generate, parse_input_to_almanac, part1, part2, solve, Almanac, Category, Day05, Map,
+    };
+    use crate::fixtures::examples;
+    use crate::prop::{check, shrink_usize, shrink_vec};
+    use crate::rng::Rng;
+    use crate::{Answers, AocError, Puzzle};
 
     #[test]
     fn test_map_get_destination() {
@@ -241,47 +351,169 @@ mod tests {
         );
     }
 
-    const SAMPLE_INPUT: &str = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
+    #[test]
+    fn test_part1_sample_input() {
+        for (input, answer) in examples(Day05::YEAR, Day05::DAY, 1) {
+            assert_eq!(
+                answer,
+                part1(&parse_input_to_almanac(&input).unwrap()).unwrap()
+            );
+        }
+    }
 
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
+    #[test]
+    fn test_part2_sample_input() {
+        for (input, answer) in examples(Day05::YEAR, Day05::DAY, 2) {
+            assert_eq!(
+                answer,
+                part2(&parse_input_to_almanac(&input).unwrap()).unwrap()
+            );
+        }
+    }
 
-water-to-light map:
-88 18 7
-18 25 70
+    #[test]
+    fn test_parse_map_with_missing_number() {
+        assert!(matches!(
+            parse_input_to_almanac("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50\n"),
+            Err(AocError::Malformed {
+                day: 5,
+                line: 5,
+                column: 1,
+                ..
+            })
+        ));
+    }
 
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
+    #[test]
+    fn test_part1_no_seeds() {
+        assert!(matches!(
+            part1(&parse_input_to_almanac("seeds: \n\nseed-to-soil map:\n50 98 2").unwrap()),
+            Err(AocError::MissingAnswer {
+                day: 5,
+                part: 1,
+                ..
+            })
+        ));
+    }
 
-temperature-to-humidity map:
-0 69 1
-1 0 69
+    #[test]
+    fn test_part2_empty_and_overflowing_seed_ranges() {
+        let almanac = parse_input_to_almanac("seeds: 5 0 79 14\n\nseed-to-soil map:\n50 98 2");
+        assert_eq!(79, part2(&almanac.unwrap()).unwrap());
+        assert!(matches!(
+            part2(&parse_input_to_almanac("seeds: 5 0").unwrap()),
+            Err(AocError::MissingAnswer { part: 2, .. })
+        ));
+        let almanac = parse_input_to_almanac(&format!("seeds: {} 2", usize::MAX));
+        assert!(matches!(
+            part2(&almanac.unwrap()),
+            Err(AocError::MissingAnswer { part: 2, .. })
+        ));
+    }
 
-humidity-to-location map:
-60 56 37
-56 93 4";
+    #[test]
+    fn test_solve() {
+        let (input, _) = &examples(Day05::YEAR, Day05::DAY, 1)[0];
+        assert_eq!(
+            Answers {
+                part1: 35,
+                part2: 46
+            },
+            solve(input).unwrap()
+        );
+        assert!(matches!(
+            solve("seeds: \n\nseed-to-soil map:\n50 98 2"),
+            Err(AocError::MissingAnswer { part: 1, .. })
+        ));
+    }
 
     #[test]
-    fn test_part1_sample_input() {
-        assert_eq!(35, part1(SAMPLE_INPUT));
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 20);
+        assert_eq!(input, generate(&mut Rng::new(1), 20));
+        let almanac = parse_input_to_almanac(&input).unwrap();
+        assert_eq!(20, almanac.seeds.len());
+        assert_eq!(7, almanac.categories.len());
+        assert!(almanac.categories.iter().all(|c| c.maps.len() == 20));
+        Day05::solve(&input).unwrap();
+    }
+
+    // Simpler maps, which always map at least one number like the puzzle's
+    fn shrink_map(map: &Map) -> Vec<Map> {
+        let mut simpler: Vec<Map> = shrink_usize(map.destination_range_start)
+            .into_iter()
+            .map(|destination_range_start| Map {
+                destination_range_start,
+                ..*map
+            })
+            .collect();
+        simpler.extend(shrink_usize(map.source_range_start).into_iter().map(
+            |source_range_start| Map {
+                source_range_start,
+                ..*map
+            },
+        ));
+        simpler.extend(
+            shrink_usize(map.range_length)
+                .into_iter()
+                .filter(|&range_length| range_length > 0)
+                .map(|range_length| Map {
+                    range_length,
+                    ..*map
+                }),
+        );
+        simpler
     }
 
     #[test]
-    fn test_part2_sample_input() {
-        assert_eq!(46, part2(SAMPLE_INPUT));
+    fn test_point_and_range_mapping_agree() {
+        // Small numbers, so that seeds and maps often overlap
+        check(
+            |rng| {
+                let seeds: Vec<usize> = (0..rng.range(1..=5)).map(|_| rng.below(100)).collect();
+                let categories: Vec<Vec<Map>> = (0..rng.range(1..=4))
+                    .map(|_| {
+                        (0..rng.range(0..=4))
+                            .map(|_| Map {
+                                destination_range_start: rng.below(100),
+                                source_range_start: rng.below(100),
+                                range_length: rng.range(1..=30),
+                            })
+                            .collect()
+                    })
+                    .collect();
+                (seeds, categories)
+            },
+            |(seeds, categories)| {
+                let mut simpler: Vec<_> = shrink_vec(seeds, |&seed| shrink_usize(seed))
+                    .into_iter()
+                    .map(|seeds| (seeds, categories.clone()))
+                    .collect();
+                simpler.extend(
+                    shrink_vec(categories, |maps| shrink_vec(maps, shrink_map))
+                        .into_iter()
+                        .map(|categories| (seeds.clone(), categories)),
+                );
+                simpler
+            },
+            |(seeds, categories)| {
+                let categories = || {
+                    categories
+                        .iter()
+                        .map(|maps| Category { maps: maps.clone() })
+                        .collect()
+                };
+                // Part 2 reads the seeds as (start, length) pairs, so each seed is a range of one
+                let points = Almanac {
+                    seeds: seeds.clone(),
+                    categories: categories(),
+                };
+                let ranges = Almanac {
+                    seeds: seeds.iter().flat_map(|&seed| [seed, 1]).collect(),
+                    categories: categories(),
+                };
+                part1(&points).ok() == part2(&ranges).ok()
+            },
+        );
     }
 }
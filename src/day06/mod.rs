@@ -1,34 +1,75 @@
-use crate::parse_file_input;
-
-pub fn run() {
-    let input = parse_file_input(6);
-    println!("Running Day 6");
-    println!(
-        "Part 1 :: What do you get if you multiply these numbers together? --> {:?}",
-        part1(&input)
-    );
-    println!(
-        "Part 2 :: How many ways can you beat the record in this one much longer race? --> {:?}",
-        part2(&input)
-    );
-}
-
-fn part1(input: &str) -> usize {
+use crate::{rng::Rng, Answers, AocError, Implementation, LineError, Puzzle};
+
+pub struct Day06;
+
+impl Puzzle for Day06 {
+    const YEAR: u16 = 2023;
+    const DAY: u16 = 6;
+    const TITLE: &'static str = "Wait For It";
+    const QUESTIONS: [&'static str; 2] = [
+        "What do you get if you multiply these numbers together?",
+        "How many ways can you beat the record in this one much longer race?",
+    ];
+
+    type Input = RaceSheet;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        input_to_race_sheet(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part1(input, Race::num_ways_to_win))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part2(input, Race::num_ways_to_win_efficient))
+    }
+
+    fn implementations() -> Vec<Implementation<Self::Input>> {
+        vec![
+            Implementation::new(1, "brute", |sheet| Ok(part1(sheet, Race::num_ways_to_win))),
+            Implementation::new(1, "scan", |sheet| {
+                Ok(part1(sheet, Race::num_ways_to_win_efficient))
+            }),
+            Implementation::new(1, "closed-form", |sheet| {
+                Ok(part1(sheet, Race::num_ways_to_win_closed_form))
+            }),
+            Implementation::new(2, "brute", |sheet| Ok(part2(sheet, Race::num_ways_to_win))),
+            Implementation::new(2, "scan", |sheet| {
+                Ok(part2(sheet, Race::num_ways_to_win_efficient))
+            }),
+            Implementation::new(2, "closed-form", |sheet| {
+                Ok(part2(sheet, Race::num_ways_to_win_closed_form))
+            }),
+        ]
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+/// Solve both parts of the puzzle for `input`.
+pub fn solve(input: &str) -> Result<Answers, AocError> {
+    Day06::solve(input)
+}
+
+fn part1(sheet: &RaceSheet, num_ways_to_win: fn(&Race) -> usize) -> usize {
     // Get the number of ways to win for each race and take the product of all results
-    input_to_races(input)
-        .iter()
-        .map(Race::num_ways_to_win)
-        .product()
+    sheet.races.iter().map(num_ways_to_win).product()
 }
 
-fn part2(input: &str) -> usize {
-    // Parse the input as a single race and return the number of the ways to win
-    let start = std::time::Instant::now();
-    // let res = input_as_kerning_to_race(input).num_ways_to_win();
-    let num_ways_to_win = input_as_kerning_to_race(input).num_ways_to_win_efficient();
-    let duration = start.elapsed();
-    println!("Execution time: {:?}", duration);
-    num_ways_to_win
+fn part2(sheet: &RaceSheet, num_ways_to_win: fn(&Race) -> usize) -> usize {
+    // Use the input read as a single race and return the number of the ways to win
+    num_ways_to_win(&sheet.kerned_race)
+}
+
+/// The race sheet read both ways: as separate races (part 1) and, ignoring the spaces
+/// between numbers, as one long race (part 2).
+#[derive(Debug)]
+pub struct RaceSheet {
+    races: Vec<Race>,
+    kerned_race: Race,
 }
 
 #[derive(Debug)]
@@ -73,66 +114,198 @@ impl Race {
         }
         max.unwrap() - min.unwrap() + 1
     }
+
+    // Holding for t wins when t * (time - t) > record, so the winning times lie between the
+    // roots of t^2 - time * t + record. Start from the lower root and correct it for floating
+    // point error; the winners are symmetric around time / 2, so the upper end is time - min.
+    fn num_ways_to_win_closed_form(&self) -> usize {
+        let beats_record = |t: usize| t * (self.time - t) > self.current_record;
+        let half = self.time / 2;
+        let (time, record) = (self.time as f64, self.current_record as f64);
+        let root = (time - (time * time - 4.0 * record).max(0.0).sqrt()) / 2.0;
+        let mut min = (root.max(0.0) as usize).min(half);
+        while min > 0 && beats_record(min - 1) {
+            min -= 1;
+        }
+        while min <= half && !beats_record(min) {
+            min += 1;
+        }
+        match min > half {
+            true => 0, // No ways to win
+            false => self.time - 2 * min + 1,
+        }
+    }
 }
 
-fn input_to_races(input: &str) -> Vec<Race> {
-    let mut lines = input.lines();
-    let times = lines.next().map(parse_line_to_usizes).unwrap();
-    let distances = lines.next().map(parse_line_to_usizes).unwrap();
-    times
+fn input_to_race_sheet(input: &str) -> Result<RaceSheet, AocError> {
+    Ok(RaceSheet {
+        races: input_to_races(input)?,
+        kerned_race: input_as_kerning_to_race(input)?,
+    })
+}
+
+fn input_to_races(input: &str) -> Result<Vec<Race>, AocError> {
+    let (time_line, distance_line) = input_to_lines(input);
+    let times = parse_line_to_usizes(time_line).map_err(|e| e.at(Day06::DAY, 1))?;
+    let distances = parse_line_to_usizes(distance_line).map_err(|e| e.at(Day06::DAY, 2))?;
+    if times.len() != distances.len() {
+        return Err(LineError::new(
+            distance_line,
+            distance_line,
+            format!("expected {} distances", times.len()),
+        )
+        .at(Day06::DAY, 2));
+    }
+    Ok(times
+        .into_iter()
         .zip(distances)
         .map(|(time, current_record)| Race {
             time,
             current_record,
         })
-        .collect()
+        .collect())
+}
+
+fn input_as_kerning_to_race(input: &str) -> Result<Race, AocError> {
+    let (time_line, distance_line) = input_to_lines(input);
+    Ok(Race {
+        time: parse_line_as_kerning(time_line).map_err(|e| e.at(Day06::DAY, 1))?,
+        current_record: parse_line_as_kerning(distance_line).map_err(|e| e.at(Day06::DAY, 2))?,
+    })
 }
 
-fn input_as_kerning_to_race(input: &str) -> Race {
+fn input_to_lines(input: &str) -> (&str, &str) {
     let mut lines = input.lines();
-    Race {
-        time: lines
-            .next()
-            .map(parse_line)
-            .unwrap()
-            .fold(String::from(""), |acc, t| acc + t)
-            .parse::<usize>()
-            .unwrap_or_else(|e| panic!("Unable to parse number: {e}")),
-        current_record: lines
-            .next()
-            .map(parse_line)
-            .unwrap()
-            .fold(String::from(""), |acc, t| acc + t)
-            .parse::<usize>()
-            .unwrap_or_else(|e| panic!("Unable to parse number: {e}")),
-    }
+    (lines.next().unwrap_or(""), lines.next().unwrap_or(""))
+}
+
+fn parse_line(line: &str) -> Result<impl Iterator<Item = &str>, LineError> {
+    line.split_once(':')
+        .map(|(_, numbers)| numbers.split_whitespace())
+        .ok_or_else(|| LineError::new(line, line, "could not find ':'"))
 }
 
-fn parse_line(line: &str) -> impl Iterator<Item = &str> {
-    line.split(':')
-        .last()
-        .unwrap_or_else(|| panic!("Could not find ':' in line '{line}'"))
-        .split_whitespace()
+fn parse_line_to_usizes(line: &str) -> Result<Vec<usize>, LineError> {
+    parse_line(line)?
+        .map(|num| {
+            num.parse::<usize>()
+                .map_err(|e| LineError::new(line, num, format!("unable to parse number: {e}")))
+        })
+        .collect()
 }
 
-fn parse_line_to_usizes(line: &str) -> impl Iterator<Item = usize> + '_ {
-    parse_line(line).map(|num| num.parse::<usize>().unwrap())
+fn parse_line_as_kerning(line: &str) -> Result<usize, LineError> {
+    let number = parse_line(line)?.fold(String::from(""), |acc, t| acc + t);
+    number
+        .parse::<usize>()
+        .map_err(|e| LineError::new(line, &number, format!("unable to parse number: {e}")))
+}
+
+/// A sheet of `size` races, but no more than four as the kerned race of part 2 would be too long
+/// to solve. The record of each race can be beaten.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let races: Vec<(usize, usize)> = (0..size.clamp(1, 4))
+        .map(|_| {
+            let time = rng.range(10..=99);
+            let best = (time / 2) * (time - time / 2);
+            (time, rng.range(1..=best - 1))
+        })
+        .collect();
+    let (mut times, mut distances) = (String::from("Time:    "), String::from("Distance:"));
+    for (time, distance) in races {
+        let width = time.to_string().len().max(distance.to_string().len());
+        times += &format!("  {time:>width$}");
+        distances += &format!("  {distance:>width$}");
+    }
+    format!("{times}\n{distances}\n")
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day06::{part1, part2};
-
-    const SAMPLE_INPUT: &str = "Time:      7  15   30
-Distance:  9  40  200";
+    use crate::day06::{generate, input_to_race_sheet, Day06, Race};
+    use crate::fixtures::examples;
+    use crate::prop::{check, shrink_usize};
+    use crate::rng::Rng;
+    use crate::{AocError, Puzzle};
 
     #[test]
     fn test_part1_sample_input() {
-        assert_eq!(288, part1(SAMPLE_INPUT));
+        for (input, answer) in examples(Day06::YEAR, Day06::DAY, 1) {
+            let sheet = input_to_race_sheet(&input).unwrap();
+            assert_eq!(answer, Day06::part1(&sheet).unwrap());
+            for implementation in Day06::implementations().iter().filter(|i| i.part == 1) {
+                assert_eq!(answer, (implementation.solve)(&sheet).unwrap());
+            }
+        }
     }
 
     #[test]
     fn test_part2_sample_input() {
-        assert_eq!(71503, part2(SAMPLE_INPUT));
+        for (input, answer) in examples(Day06::YEAR, Day06::DAY, 2) {
+            let sheet = input_to_race_sheet(&input).unwrap();
+            assert_eq!(answer, Day06::part2(&sheet).unwrap());
+            for implementation in Day06::implementations().iter().filter(|i| i.part == 2) {
+                assert_eq!(answer, (implementation.solve)(&sheet).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_input_to_race_sheet_missing_distance() {
+        assert!(matches!(
+            input_to_race_sheet("Time:      7  15   30\nDistance:  9  40"),
+            Err(AocError::Malformed {
+                day: 6,
+                line: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 3);
+        assert_eq!(input, generate(&mut Rng::new(1), 3));
+        let sheet = input_to_race_sheet(&input).unwrap();
+        assert_eq!(3, sheet.races.len());
+        assert!(Day06::part1(&sheet).unwrap() > 0);
+        assert_eq!(
+            4,
+            input_to_race_sheet(&generate(&mut Rng::new(1), 9))
+                .unwrap()
+                .races
+                .len()
+        );
+    }
+
+    #[test]
+    fn test_num_ways_to_win_implementations_agree() {
+        check(
+            |rng| {
+                let time = rng.range(0..=200);
+                Race {
+                    time,
+                    current_record: rng.range(0..=time * time / 4 + 10),
+                }
+            },
+            |race| {
+                let times = shrink_usize(race.time).into_iter().map(|time| Race {
+                    time,
+                    current_record: race.current_record,
+                });
+                let records = shrink_usize(race.current_record)
+                    .into_iter()
+                    .map(|current_record| Race {
+                        time: race.time,
+                        current_record,
+                    });
+                times.chain(records).collect()
+            },
+            |race| {
+                let brute = race.num_ways_to_win();
+                brute == race.num_ways_to_win_efficient()
+                    && brute == race.num_ways_to_win_closed_form()
+            },
+        );
     }
 }
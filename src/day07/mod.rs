@@ -1,35 +1,56 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::HashMap,
+};
 
-use crate::parse_file_input;
+use crate::{parse_lines, rng::Rng, Answers, AocError, LineError, Puzzle};
 
-pub fn run() {
-    let input = parse_file_input(7);
-    println!("Running Day 7");
-    println!(
-        "Part 1 :: What are the total winnings? --> {:?}",
-        part1(&input)
-    );
-    println!(
-        "Part 2 :: What are the new total winnings? --> {:?}",
-        part2(&input)
-    );
+pub struct Day07;
+
+impl Puzzle for Day07 {
+    const YEAR: u16 = 2023;
+    const DAY: u16 = 7;
+    const TITLE: &'static str = "Camel Cards";
+    const QUESTIONS: [&'static str; 2] = [
+        "What are the total winnings?",
+        "What are the new total winnings?",
+    ];
+
+    type Input = Vec<Round>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        input_to_rounds(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part2(input))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+/// Solve both parts of the puzzle for `input`.
+pub fn solve(input: &str) -> Result<Answers, AocError> {
+    Day07::solve(input)
 }
 
-fn part1(input: &str) -> usize {
-    let mut rounds = input_to_rounds(input, false);
-    rounds.sort_by(|a, b| a.hand.cmp(&b.hand));
-    calculate_winnings(rounds)
+fn part1(rounds: &[Round]) -> usize {
+    total_winnings(rounds, false)
 }
 
-fn part2(input: &str) -> usize {
-    let mut rounds = input_to_rounds(input, true);
-    rounds.sort_by(|a, b| a.hand.cmp(&b.hand));
-    calculate_winnings(rounds)
+fn part2(rounds: &[Round]) -> usize {
+    total_winnings(rounds, true)
 }
 
 #[derive(Debug)]
-struct Round {
-    hand: Hand,
+pub struct Round {
+    cards: [char; 5],
     bid: usize,
 }
 
@@ -83,12 +104,7 @@ impl Ord for Hand {
 }
 
 impl Hand {
-    fn new(cards: &str, with_jokers: bool) -> Hand {
-        let cards = cards[0..5]
-            .chars()
-            .collect::<Vec<char>>()
-            .try_into()
-            .unwrap();
+    fn new(cards: [char; 5], with_jokers: bool) -> Hand {
         Hand {
             cards,
             hand_type: HandType::new(cards, with_jokers),
@@ -121,7 +137,7 @@ impl HandType {
             num_jokers = card_occurrences.remove(&'J').unwrap_or(0);
         }
         let mut card_occurrences = card_occurrences.into_iter().collect::<Vec<(char, u32)>>();
-        card_occurrences.sort_by(|a, b| b.1.cmp(&a.1));
+        card_occurrences.sort_by_key(|c| Reverse(c.1));
         // Add the jokers back in, treating them as wildcards to the most frequent occurrence
         if num_jokers > 0 {
             card_occurrences[0].1 += num_jokers;
@@ -143,45 +159,179 @@ impl HandType {
     }
 }
 
-fn input_to_rounds(input: &str, with_jokers: bool) -> Vec<Round> {
-    input
-        .lines()
-        .map(|line| Round {
-            hand: Hand::new(&line[0..5], with_jokers),
-            bid: line[6..]
+fn input_to_rounds(input: &str) -> Result<Vec<Round>, AocError> {
+    parse_lines(Day07::DAY, input, |line| {
+        let (cards, bid) = line
+            .split_once(' ')
+            .ok_or_else(|| LineError::new(line, line, "expected '<cards> <bid>'"))?;
+        if let Some(card) = cards
+            .matches(|c| !CARD_VALUES_ORDERED_NO_JOKERS.contains(c))
+            .next()
+        {
+            return Err(LineError::new(line, card, "unexpected card"));
+        }
+        Ok(Round {
+            cards: cards
+                .chars()
+                .collect::<Vec<char>>()
+                .try_into()
+                .map_err(|_| LineError::new(line, cards, "expected 5 cards"))?,
+            bid: bid
                 .parse()
-                .unwrap_or_else(|e| panic!("Unable to parse bid from line '{line}': {e}")),
+                .map_err(|e| LineError::new(line, bid, format!("unable to parse bid: {e}")))?,
         })
-        .collect()
+    })
+}
+
+fn total_winnings(rounds: &[Round], with_jokers: bool) -> usize {
+    let mut hands: Vec<(Hand, usize)> = rounds
+        .iter()
+        .map(|round| (Hand::new(round.cards, with_jokers), round.bid))
+        .collect();
+    hands.sort_by(|a, b| a.0.cmp(&b.0));
+    calculate_winnings(
+        &hands
+            .into_iter()
+            .map(|(_, bid)| bid)
+            .collect::<Vec<usize>>(),
+    )
 }
 
-fn calculate_winnings(sorted_rounds: Vec<Round>) -> usize {
+// `sorted_bids` is ordered from the strongest hand to the weakest
+fn calculate_winnings(sorted_bids: &[usize]) -> usize {
     let mut total_winnings = 0;
 
-    for (i, round) in sorted_rounds.iter().rev().enumerate() {
-        total_winnings += round.bid * (i + 1);
+    for (i, bid) in sorted_bids.iter().rev().enumerate() {
+        total_winnings += bid * (i + 1);
     }
 
     total_winnings
 }
 
+/// A hand and a bid of up to 1000 per line. Each hand is drawn from a few kinds of card, so that
+/// every type of hand turns up.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut kinds: Vec<char> = CARD_VALUES_ORDERED_NO_JOKERS.chars().collect();
+        rng.shuffle(&mut kinds);
+        let kinds = &kinds[..rng.range(1..=5)];
+        let hand: String = (0..5).map(|_| *rng.choose(kinds)).collect();
+        input += &format!("{hand} {}\n", rng.range(1..=1000));
+    }
+    input
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::day07::{part1, part2};
-
-    const SAMPLE_INPUT: &str = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
+    use crate::day07::{
+        generate, input_to_rounds, part1, part2, Day07, HandType, Round,
+        CARD_VALUES_ORDERED_WITH_JOKERS,
+    };
+    use crate::fixtures::examples;
+    use crate::prop::{check, shrink_usize, shrink_vec};
+    use crate::rng::Rng;
+    use crate::{AocError, Puzzle};
 
     #[test]
     fn test_part1_sample_input() {
-        assert_eq!(6440, part1(SAMPLE_INPUT))
+        for (input, answer) in examples(Day07::YEAR, Day07::DAY, 1) {
+            assert_eq!(answer, part1(&input_to_rounds(&input).unwrap()));
+        }
     }
 
     #[test]
     fn test_part2_sample_input() {
-        assert_eq!(5905, part2(SAMPLE_INPUT));
+        for (input, answer) in examples(Day07::YEAR, Day07::DAY, 2) {
+            assert_eq!(answer, part2(&input_to_rounds(&input).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_input_to_rounds_unexpected_card() {
+        assert!(matches!(
+            input_to_rounds("32T3K 765\nT55X5 684"),
+            Err(AocError::Malformed {
+                day: 7,
+                line: 2,
+                column: 4,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 100);
+        assert_eq!(input, generate(&mut Rng::new(1), 100));
+        assert_eq!(100, input_to_rounds(&input).unwrap().len());
+        Day07::solve(&input).unwrap();
+    }
+
+    // Cards from a few of `kinds`, so that every type of hand turns up
+    fn random_cards(rng: &mut Rng, kinds: &[char]) -> [char; 5] {
+        let kinds = &kinds[..rng.range(1..=kinds.len())];
+        [(); 5].map(|_| *rng.choose(kinds))
+    }
+
+    // Simpler hands, with a card replaced by a '2'
+    fn shrink_cards(cards: &[char; 5]) -> Vec<[char; 5]> {
+        (0..5)
+            .filter(|&i| cards[i] != '2')
+            .map(|i| {
+                let mut simpler = *cards;
+                simpler[i] = '2';
+                simpler
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_joker_rule_picks_best_substitution() {
+        check(
+            |rng| random_cards(rng, &['J', 'A', 'K', '2', '3']),
+            shrink_cards,
+            |&cards| {
+                let best = CARD_VALUES_ORDERED_WITH_JOKERS
+                    .chars()
+                    .filter(|&card| card != 'J')
+                    .map(|card| {
+                        HandType::new(cards.map(|c| if c == 'J' { card } else { c }), false)
+                    })
+                    .min();
+                Some(HandType::new(cards, true)) == best
+            },
+        );
+    }
+
+    #[test]
+    fn test_parts_agree_without_jokers() {
+        check(
+            |rng| {
+                (0..rng.range(0..=20))
+                    .map(|_| {
+                        (
+                            random_cards(rng, &['A', 'K', 'Q', 'T', '2']),
+                            rng.range(1..=1000),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            },
+            |rounds| {
+                shrink_vec(rounds, |&(cards, bid)| {
+                    let mut simpler: Vec<_> =
+                        shrink_cards(&cards).into_iter().map(|c| (c, bid)).collect();
+                    simpler.extend(shrink_usize(bid).into_iter().map(|b| (cards, b)));
+                    simpler
+                })
+            },
+            |rounds| {
+                let rounds: Vec<Round> = rounds
+                    .iter()
+                    .map(|&(cards, bid)| Round { cards, bid })
+                    .collect();
+                part1(&rounds) == part2(&rounds)
+            },
+        );
     }
 }
@@ -1,56 +1,165 @@
 use num::integer::lcm;
-use std::{cmp::Ordering, collections::HashMap};
-
-use crate::parse_file_input;
-
-pub fn run() {
-    let input = parse_file_input(8);
-    println!("Running Day 8");
-    println!(
-        "Part 1 :: How many steps are required to reach ZZZ? --> {:?}",
-        part1(&input)
-    );
-    println!("Part 2 :: How many steps does it take before you're only on nodes that end with Z? --> {:?}", part2(&input));
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+use crate::{rng::Rng, trace, trace::Level, Answers, AocError, LineError, Puzzle};
+
+pub struct Day08;
+
+impl Puzzle for Day08 {
+    const YEAR: u16 = 2023;
+    const DAY: u16 = 8;
+    const TITLE: &'static str = "Haunted Wasteland";
+    const QUESTIONS: [&'static str; 2] = [
+        "How many steps are required to reach ZZZ?",
+        "How many steps does it take before you're only on nodes that end with Z?",
+    ];
+
+    type Input = Network;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+/// Solve both parts of the puzzle for `input`.
+pub fn solve(input: &str) -> Result<Answers, AocError> {
+    Day08::solve(input)
 }
 
-fn part1(input: &str) -> usize {
-    let (instructions, node_maps, _) = parse_input(input);
+fn part1(network: &Network) -> Result<usize, AocError> {
+    for node in ["AAA", "ZZZ"] {
+        if !network.node_maps.contains_key(node) {
+            return Err(no_answer(1, format!("there is no '{node}' node")));
+        }
+    }
     // Simpler case, get the number of steps for the known 'AAA' start node with known 'ZZZ' end node
-    get_num_steps("AAA", &instructions, &node_maps, |n| n == "ZZZ")
+    get_num_steps("AAA", &network.instructions, &network.node_maps, |n| {
+        n == "ZZZ"
+    })
+    .ok_or_else(|| no_answer(1, String::from("'AAA' never reaches 'ZZZ'")))
 }
 
-fn part2(input: &str) -> usize {
-    // `parse_input` returns starting nodes that begin with 'A'
-    let (instructions, node_maps, nodes) = parse_input(input);
+fn part2(network: &Network) -> Result<usize, AocError> {
+    if network.starting_nodes.is_empty() {
+        return Err(no_answer(
+            2,
+            String::from("there are no nodes that end with 'A'"),
+        ));
+    }
     // Find the number of steps for each starting node
-    let nodes: Vec<usize> = nodes
-        .into_iter()
-        .map(|n| get_num_steps(&n, &instructions, &node_maps, |n| n.ends_with('Z')))
-        .collect();
+    let nodes: Vec<usize> = network
+        .starting_nodes
+        .iter()
+        .map(|n| {
+            let steps = get_num_steps(n, &network.instructions, &network.node_maps, |n| {
+                n.ends_with('Z')
+            })
+            .ok_or_else(|| {
+                no_answer(2, format!("'{n}' never reaches a node that ends with 'Z'"))
+            })?;
+            trace!(
+                Day08::DAY,
+                Level::Info,
+                "{n} reaches a Z node in {steps} steps"
+            );
+            Ok(steps)
+        })
+        .collect::<Result<_, _>>()?;
     // Find the lowest common multiple of all starting node steps, which is the first time
     // they'll all be at their destination nodes after the same number of steps
-    lowest_common_multiple(nodes)
+    Ok(lowest_common_multiple(nodes))
 }
 
-fn parse_input(input: &str) -> (String, HashMap<String, (String, String)>, Vec<String>) {
-    let mut lines = input.lines();
-    let instructions = lines
-        .next()
-        .unwrap_or_else(|| panic!("Unable to parse first line from '{input}'"));
+fn no_answer(part: u8, reason: String) -> AocError {
+    AocError::MissingAnswer {
+        day: Day08::DAY,
+        part,
+        reason,
+    }
+}
+
+#[derive(Debug)]
+pub struct Network {
+    instructions: String,
+    node_maps: HashMap<String, (String, String)>,
+    // Nodes that end with 'A'
+    starting_nodes: Vec<String>,
+}
+
+fn parse_input(input: &str) -> Result<Network, AocError> {
+    let mut lines = input.lines().enumerate();
+    let instructions = lines.next().map_or("", |(_, line)| line);
+    if instructions.is_empty() {
+        return Err(
+            LineError::new(instructions, instructions, "expected instructions").at(Day08::DAY, 1),
+        );
+    }
+    if let Some(instruction) = instructions.matches(|c| c != 'L' && c != 'R').next() {
+        return Err(
+            LineError::new(instructions, instruction, "unexpected instruction").at(Day08::DAY, 1),
+        );
+    }
+
     let mut starting_nodes = vec![];
-    let element_maps = lines
-        .skip(1)
-        .map(|line| {
-            if line[0..3].ends_with('A') {
-                starting_nodes.push(line[0..3].to_string());
+    let mut element_maps = HashMap::new();
+    let mut node_lines = vec![];
+    if let Some((i, line)) = lines.next().filter(|(_, line)| !line.trim().is_empty()) {
+        return Err(LineError::new(line, line, "expected a blank line").at(Day08::DAY, i + 1));
+    }
+    for (i, line) in lines {
+        let (node, next_nodes) = line_to_node(line).map_err(|e| e.at(Day08::DAY, i + 1))?;
+        if node.ends_with('A') {
+            starting_nodes.push(node.to_string());
+        }
+        element_maps.insert(
+            node.to_string(),
+            (next_nodes.0.to_string(), next_nodes.1.to_string()),
+        );
+        node_lines.push((i, line, next_nodes));
+    }
+
+    // Make sure every node that can be stepped to exists
+    for (i, line, (left, right)) in node_lines {
+        for next_node in [left, right] {
+            if !element_maps.contains_key(next_node) {
+                return Err(LineError::new(line, next_node, "unknown node").at(Day08::DAY, i + 1));
             }
-            (
-                line[0..3].to_string(),
-                (line[7..10].to_string(), line[12..15].to_string()),
-            )
-        })
-        .collect();
-    (instructions.to_string(), element_maps, starting_nodes)
+        }
+    }
+
+    Ok(Network {
+        instructions: instructions.to_string(),
+        node_maps: element_maps,
+        starting_nodes,
+    })
+}
+
+// Parse a line like 'AAA = (BBB, CCC)' into the node and its (left, right) next nodes
+fn line_to_node(line: &str) -> Result<(&str, (&str, &str)), LineError> {
+    let (node, next_nodes) = line
+        .split_once(" = ")
+        .ok_or_else(|| LineError::new(line, line, "could not find ' = '"))?;
+    let next_nodes = next_nodes
+        .strip_prefix('(')
+        .and_then(|n| n.strip_suffix(')'))
+        .and_then(|n| n.split_once(", "))
+        .ok_or_else(|| LineError::new(line, next_nodes, "expected '(<left>, <right>)'"))?;
+    Ok((node, next_nodes))
 }
 
 fn get_instruction(instructions: &str, index: usize) -> char {
@@ -74,69 +183,202 @@ fn get_next_node(
     }
 }
 
+// The number of steps from `node` to a destination, or None if the path loops without reaching
+// one, which is noticed when it's back on a node at the same point in the instructions
 fn get_num_steps(
     node: &str,
     instructions: &str,
     node_maps: &HashMap<String, (String, String)>,
     is_destination: fn(&str) -> bool,
-) -> usize {
+) -> Option<usize> {
     let mut step_count = 0;
     let mut cur_node = String::from(node);
+    let mut seen = HashSet::new();
     while !is_destination(&cur_node) {
+        if !seen.insert((cur_node.clone(), step_count % instructions.len())) {
+            return None;
+        }
         cur_node = get_next_node(
             &cur_node,
             get_instruction(instructions, step_count),
             node_maps,
         );
         step_count += 1;
-        // println!("step: {step_count}, cur_element: {cur_node}");
+        trace!(
+            Day08::DAY,
+            Level::Trace,
+            "step: {step_count}, cur_element: {cur_node}"
+        );
     }
-    step_count
+    Some(step_count)
 }
 
 fn lowest_common_multiple(nums: Vec<usize>) -> usize {
     match nums.len().cmp(&2) {
-        Ordering::Less => panic!("nums length is < 2"),
+        Ordering::Less => *nums.first().expect("nums is empty"),
         Ordering::Greater => lcm(nums[0], lowest_common_multiple(nums[1..].to_vec())),
         _ => lcm(nums[0], nums[1]),
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::day08::{lowest_common_multiple, part1, part2};
+/// A network where, like the puzzle's, the ghost starting at each node that ends with `A`
+/// follows its own loop through a node that ends with `Z`, with `AAA` looping through `ZZZ`.
+/// Each loop is a multiple of the instructions long, so part 2 is the lowest common multiple of
+/// the loop lengths. There are `size` instructions, or fewer if there aren't enough node names
+/// for loops that long.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let multiples: Vec<usize> = (0..rng.range(1..=6))
+        .map(|_| *rng.choose(&[2, 3, 5, 7, 11, 13]))
+        .collect();
+    let length = size.clamp(1, 12_000 / multiples.iter().sum::<usize>());
+    let instructions: Vec<char> = (0..length).map(|_| *rng.choose(&['L', 'R'])).collect();
 
-    const SAMPLE_INPUT: &str = "LLR
+    let mut used = HashSet::from([String::from("AAA"), String::from("ZZZ")]);
+    let mut name = |rng: &mut Rng, last: char| loop {
+        let first = char::from(b'A' + rng.below(26) as u8);
+        let second = char::from(b'A' + rng.below(26) as u8);
+        let name = String::from_iter([first, second, last]);
+        if used.insert(name.clone()) {
+            return name;
+        }
+    };
+    // The node each node leads to in the direction its step in the loop takes
+    let mut steps: Vec<(String, char, String)> = Vec::new();
+    for (ghost, multiple) in multiples.into_iter().enumerate() {
+        let mut path = vec![match ghost {
+            0 => String::from("AAA"),
+            _ => name(rng, 'A'),
+        }];
+        for _ in 1..multiple * length {
+            // Nodes along the way end with anything but A or Z
+            let last = char::from(b'B' + rng.below(24) as u8);
+            path.push(name(rng, last));
+        }
+        path.push(match ghost {
+            0 => String::from("ZZZ"),
+            _ => name(rng, 'Z'),
+        });
+        // The Z node is on the first instruction again, so it leads where the start node did
+        for (i, node) in path.iter().enumerate() {
+            let next = path.get(i + 1).unwrap_or(&path[1]);
+            steps.push((node.clone(), instructions[i % length], next.clone()));
+        }
+    }
 
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ";
+    // The direction that isn't taken can lead anywhere
+    let nodes: Vec<String> = steps.iter().map(|(node, _, _)| node.clone()).collect();
+    let mut lines: Vec<String> = steps
+        .into_iter()
+        .map(|(node, direction, next)| {
+            let other = rng.choose(&nodes);
+            match direction {
+                'L' => format!("{node} = ({next}, {other})"),
+                _ => format!("{node} = ({other}, {next})"),
+            }
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    format!(
+        "{}\n\n{}\n",
+        String::from_iter(instructions),
+        lines.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day08::{generate, lowest_common_multiple, parse_input, part1, part2, Day08};
+    use crate::fixtures::examples;
+    use crate::rng::Rng;
+    use crate::{AocError, Puzzle};
 
     #[test]
     fn test_part1_sample_input() {
-        assert_eq!(6, part1(SAMPLE_INPUT));
+        for (input, answer) in examples(Day08::YEAR, Day08::DAY, 1) {
+            assert_eq!(answer, part1(&parse_input(&input).unwrap()).unwrap());
+        }
     }
 
-    const SAMPLE_INPUT_P2: &str = "LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)";
-
     #[test]
     fn test_part2_sample_input() {
-        assert_eq!(6, part2(SAMPLE_INPUT_P2));
+        for (input, answer) in examples(Day08::YEAR, Day08::DAY, 2) {
+            assert_eq!(answer, part2(&parse_input(&input).unwrap()).unwrap());
+        }
     }
 
     #[test]
     fn test_lowest_common_multiple() {
+        assert_eq!(7, lowest_common_multiple(vec![7]));
         assert_eq!(6, lowest_common_multiple(vec![2, 3]));
         assert_eq!(1000, lowest_common_multiple(vec![2, 100, 1000]));
         assert_eq!(90, lowest_common_multiple(vec![2, 3, 5, 9]));
     }
+
+    #[test]
+    fn test_parse_input_unknown_node() {
+        assert!(matches!(
+            parse_input("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)"),
+            Err(AocError::Malformed {
+                day: 8,
+                line: 4,
+                column: 13,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_part1_without_start_node() {
+        assert!(matches!(
+            part1(&parse_input(&examples(Day08::YEAR, Day08::DAY, 2)[0].0).unwrap()),
+            Err(AocError::MissingAnswer {
+                day: 8,
+                part: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_input_second_line_not_blank() {
+        assert!(matches!(
+            parse_input("LR\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)"),
+            Err(AocError::Malformed {
+                day: 8,
+                line: 2,
+                column: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_unreachable_destination() {
+        let network = parse_input("LR\n\nAAA = (AAA, AAA)").unwrap();
+        assert!(matches!(
+            part1(&network),
+            Err(AocError::MissingAnswer { part: 1, .. })
+        ));
+        let network = parse_input("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)");
+        assert!(matches!(
+            part1(&network.unwrap()),
+            Err(AocError::MissingAnswer { part: 1, .. })
+        ));
+        let network = parse_input("LR\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\nBBA = (BBA, BBA)");
+        assert!(matches!(
+            part2(&network.unwrap()),
+            Err(AocError::MissingAnswer { part: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 50);
+        assert_eq!(input, generate(&mut Rng::new(1), 50));
+        let network = parse_input(&input).unwrap();
+        let length = network.instructions.len();
+        assert_eq!(50, length);
+        assert_eq!(0, part1(&network).unwrap() % length);
+        assert_eq!(0, part2(&network).unwrap() % length);
+    }
 }
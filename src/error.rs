@@ -0,0 +1,177 @@
+use std::{error::Error, fmt, io, path::PathBuf};
+
+/// Errors that can occur while reading, parsing or solving a puzzle.
+#[derive(Debug)]
+pub enum AocError {
+    /// A file could not be read or written.
+    Io { path: PathBuf, source: io::Error },
+    /// A line of puzzle input did not have the expected format.
+    Malformed {
+        day: u16,
+        /// 1-based line number within the input
+        line: usize,
+        /// 1-based column of `text` within the line
+        column: usize,
+        text: String,
+        reason: String,
+    },
+    /// The input was well-formed, but a part has no answer for it.
+    MissingAnswer { day: u16, part: u8, reason: String },
+    /// The command line arguments were invalid.
+    Usage(String),
+    /// Required configuration, such as the session cookie, is missing.
+    Config(String),
+    /// A request to the Advent of Code site failed.
+    Request { url: String, reason: String },
+    /// An answer was not submitted because it is known to be wrong.
+    Refused(String),
+    /// Some answers no longer match the known answers.
+    Regression(usize),
+    /// Some stages are slower than their saved benchmark results.
+    PerformanceRegression(usize),
+    /// Some days could not be read, parsed or solved when running several.
+    Failed(usize),
+    /// A day's jobs stopped without reporting back, because one of them panicked.
+    Unfinished(u16),
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io { path, source } => {
+                write!(f, "Unable to access '{}': {source}", path.display())
+            }
+            AocError::Malformed {
+                day,
+                line,
+                column,
+                text,
+                reason,
+            } => write!(
+                f,
+                "Malformed input for day {day} at line {line}, column {column}: {reason} (found '{text}')"
+            ),
+            AocError::MissingAnswer { day, part, reason } => {
+                write!(f, "No answer for day {day} part {part}: {reason}")
+            }
+            AocError::Usage(message) | AocError::Config(message) => write!(f, "{message}"),
+            AocError::Refused(reason) => write!(f, "Refusing to submit: {reason}"),
+            AocError::Regression(count) => {
+                write!(f, "{count} answer(s) do not match the known answers")
+            }
+            AocError::PerformanceRegression(count) => {
+                write!(f, "{count} stage(s) are slower than the saved benchmark results")
+            }
+            AocError::Failed(count) => write!(f, "{count} day(s) failed"),
+            AocError::Unfinished(day) => write!(f, "Day {day} did not finish"),
+            AocError::Request { url, reason } => write!(f, "Request to {url} failed: {reason}"),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A problem with a single line of input, before it is known which day and line number it
+/// belongs to. Use [`LineError::at`] to turn it into an [`AocError`].
+#[derive(Debug, PartialEq)]
+pub struct LineError {
+    column: usize,
+    text: String,
+    reason: String,
+}
+
+impl LineError {
+    /// `text` should be a slice of `line`, its position is used to find the column.
+    pub fn new(line: &str, text: &str, reason: impl Into<String>) -> LineError {
+        LineError {
+            column: column_of(line, text),
+            text: text.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Shift the column for an error found in `part`, a slice of `line`.
+    pub fn within(self, line: &str, part: &str) -> LineError {
+        LineError {
+            column: self.column + column_of(line, part) - 1,
+            ..self
+        }
+    }
+
+    pub fn at(self, day: u16, line: usize) -> AocError {
+        AocError::Malformed {
+            day,
+            line,
+            column: self.column,
+            text: self.text,
+            reason: self.reason,
+        }
+    }
+}
+
+// 1-based column of `text` in `line`. Falls back to searching for `text` if it isn't a slice
+// of `line`.
+fn column_of(line: &str, text: &str) -> usize {
+    let line_start = line.as_ptr() as usize;
+    let text_start = text.as_ptr() as usize;
+    if text_start >= line_start && text_start + text.len() <= line_start + line.len() {
+        text_start - line_start + 1
+    } else {
+        line.find(text).map_or(1, |i| i + 1)
+    }
+}
+
+/// Parse each line of `input` with `parse_line`, attaching the day and line number to any error.
+pub fn parse_lines<T, F>(day: u16, input: &str, parse_line: F) -> Result<Vec<T>, AocError>
+where
+    F: Fn(&str) -> Result<T, LineError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(line).map_err(|e| e.at(day, i + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{parse_lines, AocError, LineError};
+
+    #[test]
+    fn test_line_error_column() {
+        let line = "Game 12: 3 blue";
+        assert_eq!(6, LineError::new(line, &line[5..7], "").column);
+        assert_eq!(10, LineError::new(line, "3", "").column);
+        assert_eq!(
+            10,
+            LineError::new("3 blue", "3", "")
+                .within(line, &line[9..])
+                .column
+        );
+    }
+
+    #[test]
+    fn test_parse_lines_reports_line_number() {
+        let result = parse_lines(4, "1\n2\nx3\n4", |line| {
+            line.parse::<usize>()
+                .map_err(|_| LineError::new(line, &line[0..1], "expected a digit"))
+        });
+        match result {
+            Err(AocError::Malformed {
+                day,
+                line,
+                column,
+                text,
+                ..
+            }) => assert_eq!((4, 3, 1, "x"), (day, line, column, text.as_str())),
+            other => panic!("Unexpected result {other:?}"),
+        }
+    }
+}
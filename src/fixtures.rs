@@ -0,0 +1,295 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::AocError;
+
+/// An example input from a puzzle page, and the answer the page gives for it.
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub part: u8,
+    pub input: String,
+    pub answer: Option<usize>,
+}
+
+/// Find the examples in a saved puzzle page. Each `<pre><code>` block in a part's `<article>` is
+/// an example, answered by the last emphasized number (`<code><em>42</em></code>`) before the
+/// next block. A part with no blocks of its own is answered for the first example of the part
+/// before it.
+pub fn extract_examples(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+    for (part, article) in (1..).zip(articles(html)) {
+        let blocks = tagged(article, "<pre><code>", "</code></pre>");
+        let answers: Vec<(usize, usize)> = tagged(article, "<code><em>", "</em></code>")
+            .into_iter()
+            .filter_map(|(start, _, text)| Some((start, decode(&text).trim().parse().ok()?)))
+            .collect();
+        let answer_between = |start: usize, end: usize| {
+            answers
+                .iter()
+                .rev()
+                .find(|(i, _)| (start..end).contains(i))
+                .map(|(_, answer)| *answer)
+        };
+
+        if blocks.is_empty() {
+            let previous = examples.iter().find(|e| e.part == part - 1);
+            if let Some(previous) = previous {
+                examples.push(Example {
+                    part,
+                    input: previous.input.clone(),
+                    answer: answer_between(0, article.len()),
+                });
+            }
+            continue;
+        }
+        for (i, (_, end, text)) in blocks.iter().enumerate() {
+            let next_start = blocks.get(i + 1).map_or(article.len(), |b| b.0);
+            examples.push(Example {
+                part,
+                input: decode(&strip_tags(text)),
+                answer: answer_between(*end, next_start),
+            });
+        }
+    }
+    examples
+}
+
+/// The `<article>` elements of a page, one for each part that is unlocked, or the whole page if
+/// there are none.
+fn articles(html: &str) -> Vec<&str> {
+    let articles: Vec<&str> = html
+        .split("<article")
+        .skip(1)
+        .map(|rest| rest.split("</article>").next().unwrap_or(rest))
+        .collect();
+    match articles.is_empty() {
+        true => vec![html],
+        false => articles,
+    }
+}
+
+/// The start, end and contents of each occurrence of text between `open` and `close`.
+fn tagged(html: &str, open: &str, close: &str) -> Vec<(usize, usize, String)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = html[offset..].find(open).map(|i| offset + i) {
+        let contents_start = start + open.len();
+        let Some(contents_end) = html[contents_start..]
+            .find(close)
+            .map(|i| contents_start + i)
+        else {
+            break;
+        };
+        offset = contents_end + close.len();
+        found.push((
+            start,
+            offset,
+            html[contents_start..contents_end].to_string(),
+        ));
+    }
+    found
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn decode(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The directory the fixtures for a day are kept in under `root`.
+pub fn fixtures_dir(root: &Path, year: u16, day: u16) -> PathBuf {
+    root.join("fixtures")
+        .join(year.to_string())
+        .join(format!("day{day:02}"))
+}
+
+/// Write each example to `partP-N.txt` in `dir`, with its answer in `partP-N.answer`, replacing
+/// any examples already there. Returns the paths that were written.
+pub fn write_examples(dir: &Path, examples: &[Example]) -> Result<Vec<PathBuf>, AocError> {
+    fs::create_dir_all(dir).map_err(io_error(dir))?;
+    for entry in fs::read_dir(dir).map_err(io_error(dir))? {
+        let path = entry.map_err(io_error(dir))?.path();
+        if example_name(&path).is_some() {
+            fs::remove_file(&path).map_err(io_error(&path))?;
+        }
+    }
+
+    let mut written = Vec::new();
+    for part in [1, 2] {
+        for (n, example) in (1..).zip(examples.iter().filter(|e| e.part == part)) {
+            let path = dir.join(format!("part{part}-{n}.txt"));
+            fs::write(&path, &example.input).map_err(io_error(&path))?;
+            written.push(path);
+            if let Some(answer) = example.answer {
+                let path = dir.join(format!("part{part}-{n}.answer"));
+                fs::write(&path, format!("{answer}\n")).map_err(io_error(&path))?;
+                written.push(path);
+            }
+        }
+    }
+    Ok(written)
+}
+
+/// Load the examples for a part from `dir`, in the order they appeared on the page.
+pub fn load_examples(dir: &Path, part: u8) -> Result<Vec<Example>, AocError> {
+    let read = |path: &Path| fs::read_to_string(path).map_err(io_error(path));
+    let mut numbered = Vec::new();
+    for entry in fs::read_dir(dir).map_err(io_error(dir))? {
+        let path = entry.map_err(io_error(dir))?.path();
+        match example_name(&path) {
+            Some((p, n, "txt")) if p == part => numbered.push((n, path)),
+            _ => {}
+        }
+    }
+    numbered.sort();
+
+    numbered
+        .into_iter()
+        .map(|(_, path)| {
+            let answer_path = path.with_extension("answer");
+            let answer = match answer_path.exists() {
+                true => Some(read(&answer_path)?.trim().parse().map_err(|e| {
+                    AocError::Config(format!(
+                        "Invalid answer in '{}': {e}",
+                        answer_path.display()
+                    ))
+                })?),
+                false => None,
+            };
+            Ok(Example {
+                part,
+                input: read(&path)?,
+                answer,
+            })
+        })
+        .collect()
+}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> AocError {
+    let path = path.to_path_buf();
+    move |source| AocError::Io { path, source }
+}
+
+/// The part, number and extension of an example file named like `part1-2.txt`.
+fn example_name(path: &Path) -> Option<(u8, usize, &str)> {
+    let name = path.file_name()?.to_str()?;
+    let (stem, extension) = name.split_once('.')?;
+    let (part, n) = stem.strip_prefix("part")?.split_once('-')?;
+    Some((part.parse().ok()?, n.parse().ok()?, extension))
+}
+
+/// The inputs and answers of the examples in this crate's fixtures for a part, for use in tests.
+/// Panics if there are none, so a missing fixture can't make a test pass.
+#[cfg(test)]
+pub(crate) fn examples(year: u16, day: u16, part: u8) -> Vec<(String, usize)> {
+    let dir = fixtures_dir(Path::new(env!("CARGO_MANIFEST_DIR")), year, day);
+    let examples: Vec<_> = load_examples(&dir, part)
+        .unwrap()
+        .into_iter()
+        .filter_map(|example| Some((example.input, example.answer?)))
+        .collect();
+    assert!(
+        !examples.is_empty(),
+        "No examples with answers for {year} day {day} part {part} in {}",
+        dir.display()
+    );
+    examples
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use crate::fixtures::{extract_examples, load_examples, write_examples, Example};
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 8: Haunted Wasteland ---</h2>
+<p>For example:</p>
+<pre><code>RL
+
+AAA = (BBB, CCC)
+CCC = (ZZZ, GGG)
+</code></pre>
+<p>Starting with <code>AAA</code>, you need to look up the next element, reaching <code>ZZZ</code> in <code><em>2</em></code> steps.</p>
+<pre><code>LLR
+
+<em>AAA</em> = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+</code></pre>
+<p>This takes <code><em>6</em></code> steps.</p>
+<p>How many steps are required to reach <code>ZZZ</code>?</p>
+</article>
+<p>Your puzzle answer was <code>12345</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Starting at every node that ends with <code>A</code> takes <code><em>6</em></code> steps &amp; no more.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_extract_examples() {
+        let examples = extract_examples(PAGE);
+        assert_eq!(
+            vec![
+                Example {
+                    part: 1,
+                    input: String::from("RL\n\nAAA = (BBB, CCC)\nCCC = (ZZZ, GGG)\n"),
+                    answer: Some(2)
+                },
+                Example {
+                    part: 1,
+                    input: String::from(
+                        "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n"
+                    ),
+                    answer: Some(6)
+                },
+                Example {
+                    part: 2,
+                    input: String::from("RL\n\nAAA = (BBB, CCC)\nCCC = (ZZZ, GGG)\n"),
+                    answer: Some(6)
+                },
+            ],
+            examples
+        );
+    }
+
+    #[test]
+    fn test_extract_examples_decodes_entities() {
+        let examples = extract_examples("<pre><code>a &lt;b&gt; &amp;c\n</code></pre>");
+        assert_eq!("a <b> &c\n", examples[0].input);
+        assert_eq!(None, examples[0].answer);
+    }
+
+    #[test]
+    fn test_write_and_load_examples() {
+        let dir = env::temp_dir().join(format!("aoc2023-fixtures-{}", std::process::id()));
+        let examples = extract_examples(PAGE);
+        assert_eq!(6, write_examples(&dir, &examples).unwrap().len());
+        assert_eq!(examples[..2], load_examples(&dir, 1).unwrap());
+        assert_eq!(examples[2..], load_examples(&dir, 2).unwrap());
+
+        // Writing again replaces the examples that were there
+        write_examples(&dir, &examples[2..]).unwrap();
+        assert!(load_examples(&dir, 1).unwrap().is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,271 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut, RangeInclusive},
+};
+
+/// A position in a grid as `(row, column)`, counting from the top left.
+pub type Pos = (usize, usize);
+
+const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// A row that isn't as long as the first row of the grid being built from it.
+#[derive(Debug, PartialEq)]
+pub struct RaggedRow {
+    /// 0-based index of the row
+    pub row: usize,
+    pub len: usize,
+    /// The length of the first row
+    pub width: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// A grid of `rows`, which must all be as long as the first.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Result<Grid<T>, RaggedRow> {
+        let mut grid = Grid {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+        };
+        for (row, cells) in rows.into_iter().enumerate() {
+            if row == 0 {
+                grid.width = cells.len();
+            } else if cells.len() != grid.width {
+                return Err(RaggedRow {
+                    row,
+                    len: cells.len(),
+                    width: grid.width,
+                });
+            }
+            grid.cells.extend(cells);
+            grid.height += 1;
+        }
+        Ok(grid)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        match self.contains(pos) {
+            true => Some(&mut self.cells[pos.0 * self.width + pos.1]),
+            false => None,
+        }
+    }
+
+    /// The positions above, left of, right of and below `pos` that are in the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.offsets(pos, &NEIGHBOURS_4)
+    }
+
+    /// The positions around `pos`, including diagonally, that are in the grid, row by row.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.offsets(pos, &NEIGHBOURS_8)
+    }
+
+    fn offsets(
+        &self,
+        (row, col): Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + '_ {
+        offsets.iter().filter_map(move |&(row_offset, col_offset)| {
+            let pos = (
+                row.checked_add_signed(row_offset)?,
+                col.checked_add_signed(col_offset)?,
+            );
+            self.contains(pos).then_some(pos)
+        })
+    }
+
+    /// The cells of row `row`, which must be in the grid.
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "Row {row} is outside the grid");
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    /// The cells of column `col` from top to bottom, which must be in the grid.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "Column {col} is outside the grid");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// The positions and cells in `rows` and `cols`, row by row, leaving out any outside the grid.
+    pub fn region(
+        &self,
+        rows: RangeInclusive<usize>,
+        cols: RangeInclusive<usize>,
+    ) -> impl Iterator<Item = (Pos, &T)> {
+        let rows = *rows.start()..rows.end().saturating_add(1).min(self.height);
+        let cols = *cols.start()..cols.end().saturating_add(1).min(self.width);
+        rows.flat_map(move |row| cols.clone().map(move |col| ((row, col), &self[(row, col)])))
+    }
+
+    /// Every position and cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| ((i / self.width, i % self.width), cell))
+    }
+
+    /// The positions of the cells matching `predicate`, row by row.
+    pub fn find_all<'a>(
+        &'a self,
+        predicate: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Pos> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("Position {pos:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("Position {pos:?} is outside the grid"))
+    }
+}
+
+/// Each row on its own line, with no newline after the last.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{Grid, RaggedRow};
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::from_rows(input.lines().map(|line| line.chars().collect())).unwrap()
+    }
+
+    #[test]
+    fn test_from_rows() {
+        let grid = grid("abc\ndef");
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!('f', grid[(1, 2)]);
+        assert_eq!(None, grid.get((2, 0)));
+        assert_eq!(None, grid.get((0, 3)));
+        assert_eq!(
+            Err(RaggedRow {
+                row: 2,
+                len: 2,
+                width: 3
+            }),
+            Grid::from_rows(["abc", "def", "gh"].map(|line| line.chars().collect()))
+        );
+        let empty = Grid::<char>::from_rows([]).unwrap();
+        assert_eq!((0, 0), (empty.width(), empty.height()));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid("abcd\nefgh\nijkl");
+        assert_eq!(
+            vec![(0, 1), (1, 0)],
+            grid.neighbours4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 2), (1, 1), (1, 3), (2, 2)],
+            grid.neighbours4((1, 2)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(1, 2), (1, 3), (2, 2)],
+            grid.neighbours8((2, 3)).collect::<Vec<_>>()
+        );
+        assert_eq!(8, grid.neighbours8((1, 1)).count());
+    }
+
+    #[test]
+    fn test_rows_columns_and_regions() {
+        let mut grid = grid("abcd\nefgh\nijkl");
+        assert_eq!(vec!['e', 'f', 'g', 'h'], grid.row(1));
+        assert_eq!("aei", grid.columns().next().unwrap().collect::<String>());
+        assert_eq!("dhl", grid.column(3).collect::<String>());
+        assert_eq!(
+            "cdghkl",
+            grid.region(0..=5, 2..=4)
+                .map(|(_, c)| c)
+                .collect::<String>()
+        );
+        assert_eq!(
+            vec![(2, 3)],
+            grid.find_all(|&c| c == 'l').collect::<Vec<_>>()
+        );
+
+        grid[(0, 0)] = 'z';
+        assert_eq!("zbcd\nefgh\nijkl", grid.to_string());
+        assert_eq!("..\n..", Grid::new(2, 2, '.').to_string());
+    }
+}
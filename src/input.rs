@@ -0,0 +1,107 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::AocError;
+
+/// Environment variable that overrides the directory puzzle inputs are read from.
+pub const DATA_DIR_ENV: &str = "AOC_DATA_DIR";
+
+/// Where to read a puzzle input from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    /// The day's input file in the data directory, e.g. `./data/2023/day02.txt`
+    DataDir {
+        year: u16,
+        day: u16,
+    },
+    /// A file at an explicit path
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn read(&self) -> Result<String, AocError> {
+        match self {
+            InputSource::DataDir { year, day } => read_file(input_path(*year, *day)),
+            InputSource::Path(path) => read_file(path.clone()),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|source| AocError::Io {
+                        path: PathBuf::from("<stdin>"),
+                        source,
+                    })?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// The directory puzzle inputs are kept in, `./data` unless overridden by `AOC_DATA_DIR`.
+pub fn data_dir() -> PathBuf {
+    env::var_os(DATA_DIR_ENV).map_or_else(|| PathBuf::from("./data"), PathBuf::from)
+}
+
+/// The directory in the data directory that a year's inputs are kept in.
+pub fn year_dir(year: u16) -> PathBuf {
+    data_dir().join(year.to_string())
+}
+
+/// The path of the input file for the given day in the data directory.
+pub fn input_path(year: u16, day: u16) -> PathBuf {
+    year_dir(year).join(format!("day{:02}.txt", day))
+}
+
+/// The path of the ledger of answers submitted for a year in the data directory.
+pub fn ledger_path(year: u16) -> PathBuf {
+    year_dir(year).join("ledger.txt")
+}
+
+/// The path of the known answers file for a year in the data directory.
+pub fn answers_path(year: u16) -> PathBuf {
+    year_dir(year).join("answers.toml")
+}
+
+fn read_file(path: PathBuf) -> Result<String, AocError> {
+    fs::read_to_string(&path).map_err(|source| AocError::Io { path, source })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use crate::input::{input_path, InputSource};
+    use crate::AocError;
+
+    #[test]
+    fn test_input_path() {
+        assert!(input_path(2023, 3).ends_with("2023/day03.txt"));
+        assert!(input_path(2022, 12).ends_with("2022/day12.txt"));
+    }
+
+    #[test]
+    fn test_read_path() {
+        let path = env::temp_dir().join(format!("aoc2023-input-{}.txt", std::process::id()));
+        fs::write(&path, "Time: 7\nDistance: 9").unwrap();
+        assert_eq!(
+            "Time: 7\nDistance: 9",
+            InputSource::Path(path.clone()).read().unwrap()
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_missing_path() {
+        let path = PathBuf::from("./does/not/exist.txt");
+        match InputSource::Path(path.clone()).read() {
+            Err(AocError::Io {
+                path: error_path, ..
+            }) => assert_eq!(path, error_path),
+            other => panic!("Unexpected result {other:?}"),
+        }
+    }
+}
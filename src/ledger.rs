@@ -0,0 +1,330 @@
+use std::{
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{client::Client, AocError};
+
+/// The result of submitting an answer, as reported by the site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about which direction
+    Wrong,
+    /// The answer wasn't checked because another was submitted too recently
+    RateLimited,
+    /// The answer wasn't checked because the part has already been solved
+    AlreadySolved,
+}
+
+impl Outcome {
+    /// Find the outcome in the page returned after submitting an answer.
+    pub fn from_response(body: &str) -> Option<Outcome> {
+        if body.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if body.contains("your answer is too high") {
+            Some(Outcome::TooHigh)
+        } else if body.contains("your answer is too low") {
+            Some(Outcome::TooLow)
+        } else if body.contains("That's not the right answer") {
+            Some(Outcome::Wrong)
+        } else if body.contains("You gave an answer too recently") {
+            Some(Outcome::RateLimited)
+        } else if body.contains("You don't seem to be solving the right level") {
+            Some(Outcome::AlreadySolved)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited => "rate-limited",
+            Outcome::AlreadySolved => "already-solved",
+        })
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "too-high" => Ok(Outcome::TooHigh),
+            "too-low" => Ok(Outcome::TooLow),
+            "wrong" => Ok(Outcome::Wrong),
+            "rate-limited" => Ok(Outcome::RateLimited),
+            "already-solved" => Ok(Outcome::AlreadySolved),
+            _ => Err(format!("unknown outcome '{s}'")),
+        }
+    }
+}
+
+/// A submitted answer and its outcome.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub day: u16,
+    pub part: u8,
+    pub answer: usize,
+    pub outcome: Outcome,
+}
+
+/// A record of every answer submitted for a year, kept in a file with one tab-separated
+/// `day part answer outcome` entry per line.
+pub struct Ledger {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    /// Load the ledger at `path`. A missing file is treated as an empty ledger.
+    pub fn load(path: &Path) -> Result<Ledger, AocError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(source) => {
+                return Err(AocError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        let entries = contents
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                line_to_entry(line).ok_or_else(|| {
+                    AocError::Config(format!(
+                        "Invalid entry in ledger '{}' at line {}: '{line}'",
+                        path.display(),
+                        i + 1
+                    ))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Ledger {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// The correct answer for a part, if it has been submitted.
+    pub fn correct_answer(&self, day: u16, part: u8) -> Option<usize> {
+        self.entries_for(day, part)
+            .find(|entry| entry.outcome == Outcome::Correct)
+            .map(|entry| entry.answer)
+    }
+
+    /// The reason `answer` should not be submitted, if the ledger shows it can't be correct.
+    pub fn check(&self, day: u16, part: u8, answer: usize) -> Option<String> {
+        if let Some(correct) = self.correct_answer(day, part) {
+            return Some(format!(
+                "day {day} part {part} has already been solved with {correct}"
+            ));
+        }
+        self.entries_for(day, part).find_map(|entry| {
+            let known_wrong = match entry.outcome {
+                Outcome::TooHigh => answer >= entry.answer,
+                Outcome::TooLow => answer <= entry.answer,
+                Outcome::Wrong => answer == entry.answer,
+                _ => false,
+            };
+            known_wrong.then(|| {
+                format!(
+                    "{answer} is known to be wrong, {} was {}",
+                    entry.answer, entry.outcome
+                )
+            })
+        })
+    }
+
+    /// Add an entry, appending it to the ledger file.
+    pub fn record(&mut self, entry: Entry) -> Result<(), AocError> {
+        let io_error = |source| AocError::Io {
+            path: self.path.clone(),
+            source,
+        };
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(io_error)?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}",
+            entry.day, entry.part, entry.answer, entry.outcome
+        )
+        .map_err(io_error)?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Submit an answer unless the ledger shows it can't be correct, and record the outcome.
+    pub fn submit(
+        &mut self,
+        client: &Client,
+        year: u16,
+        day: u16,
+        part: u8,
+        answer: usize,
+    ) -> Result<Outcome, AocError> {
+        if let Some(reason) = self.check(day, part, answer) {
+            return Err(AocError::Refused(reason));
+        }
+        let outcome = client.submit_answer(year, day, part, answer)?;
+        self.record(Entry {
+            day,
+            part,
+            answer,
+            outcome,
+        })?;
+        Ok(outcome)
+    }
+
+    fn entries_for(&self, day: u16, part: u8) -> impl Iterator<Item = &Entry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.day == day && entry.part == part)
+    }
+}
+
+fn line_to_entry(line: &str) -> Option<Entry> {
+    let mut fields = line.split('\t');
+    let entry = Entry {
+        day: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        answer: fields.next()?.parse().ok()?,
+        outcome: fields.next()?.parse().ok()?,
+    };
+    fields.next().is_none().then_some(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use crate::client::Client;
+    use crate::ledger::{Entry, Ledger, Outcome};
+    use crate::test_server::TestServer;
+    use crate::AocError;
+
+    fn temp_ledger_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("aoc2023-ledger-{name}-{}.txt", std::process::id()))
+    }
+
+    #[test]
+    fn test_outcome_from_response() {
+        assert_eq!(
+            Some(Outcome::Correct),
+            Outcome::from_response(
+                "<article><p>That's the right answer!  You are one gold star closer.</p></article>"
+            )
+        );
+        assert_eq!(
+            Some(Outcome::TooHigh),
+            Outcome::from_response("<p>That's not the right answer; your answer is too high.</p>")
+        );
+        assert_eq!(
+            Some(Outcome::TooLow),
+            Outcome::from_response("<p>That's not the right answer; your answer is too low.</p>")
+        );
+        assert_eq!(
+            Some(Outcome::Wrong),
+            Outcome::from_response("<p>That's not the right answer.  If you're stuck</p>")
+        );
+        assert_eq!(
+            Some(Outcome::RateLimited),
+            Outcome::from_response("<p>You gave an answer too recently; you have to wait</p>")
+        );
+        assert_eq!(None, Outcome::from_response("<p>Please log in</p>"));
+    }
+
+    #[test]
+    fn test_check() {
+        let path = temp_ledger_path("check");
+        let mut ledger = Ledger::load(&path).unwrap();
+        for (answer, outcome) in [
+            (100, Outcome::TooHigh),
+            (10, Outcome::TooLow),
+            (50, Outcome::Wrong),
+            (60, Outcome::RateLimited),
+        ] {
+            ledger
+                .record(Entry {
+                    day: 5,
+                    part: 1,
+                    answer,
+                    outcome,
+                })
+                .unwrap();
+        }
+
+        assert!(ledger.check(5, 1, 100).is_some());
+        assert!(ledger.check(5, 1, 150).is_some());
+        assert!(ledger.check(5, 1, 10).is_some());
+        assert!(ledger.check(5, 1, 50).is_some());
+        assert!(ledger.check(5, 1, 60).is_none());
+        assert!(ledger.check(5, 1, 51).is_none());
+        assert!(ledger.check(5, 2, 100).is_none());
+
+        // Reloading the file gives the same entries
+        assert_eq!(ledger.entries(), Ledger::load(&path).unwrap().entries());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = TestServer::start(|request| match request.body.as_str() {
+            "level=2&answer=46" => (200, String::from("<p>That's the right answer!</p>")),
+            _ => (
+                200,
+                String::from("<p>That's not the right answer; your answer is too low.</p>"),
+            ),
+        });
+        let client = Client::new(server.url(), "abc123");
+        let path = temp_ledger_path("submit");
+        let mut ledger = Ledger::load(&path).unwrap();
+
+        assert_eq!(
+            Outcome::TooLow,
+            ledger.submit(&client, 2023, 5, 2, 40).unwrap()
+        );
+        assert!(matches!(
+            ledger.submit(&client, 2023, 5, 2, 35),
+            Err(AocError::Refused(_))
+        ));
+        assert_eq!(
+            Outcome::Correct,
+            ledger.submit(&client, 2023, 5, 2, 46).unwrap()
+        );
+        assert!(matches!(
+            ledger.submit(&client, 2023, 5, 2, 46),
+            Err(AocError::Refused(_))
+        ));
+        assert_eq!(Some(46), Ledger::load(&path).unwrap().correct_answer(5, 2));
+
+        let requests = server.requests();
+        assert_eq!(2, requests.len());
+        assert_eq!("POST", requests[0].method);
+        assert_eq!("/2023/day/5/answer", requests[0].path);
+        fs::remove_file(path).unwrap();
+    }
+}
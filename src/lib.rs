@@ -1,5 +1,7 @@
-use std::fs;
-
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod client;
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -8,7 +10,92 @@ pub mod day05;
 pub mod day06;
 pub mod day07;
 pub mod day08;
+mod error;
+pub mod fixtures;
+pub mod grid;
+pub mod input;
+pub mod ledger;
+pub mod pool;
+#[cfg(test)]
+mod prop;
+pub mod rng;
+pub mod runner;
+pub mod scaffold;
+mod solution;
+#[cfg(test)]
+mod test_server;
+pub mod trace;
+pub mod watch;
+
+pub use error::{parse_lines, AocError, LineError};
+pub use solution::{Answers, Implementation, ParsedInput, Puzzle, Solution};
+
+/// The year of the event used when none is given.
+pub const DEFAULT_YEAR: u16 = 2023;
+
+/// Every solved day, in order of year then day.
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+];
+
+/// Find the solution for the given day of the given year, if it has been solved.
+pub fn find_solution(year: u16, day: u16) -> Option<&'static dyn Solution> {
+    solutions_for_year(year).find(|s| s.day() == day)
+}
+
+/// The solutions for every solved day of the given year, in day order.
+pub fn solutions_for_year(year: u16) -> impl Iterator<Item = &'static dyn Solution> {
+    SOLUTIONS.iter().copied().filter(move |s| s.year() == year)
+}
+
+/// The solution for the most recent solved day of the given year.
+pub fn latest_solution(year: u16) -> Option<&'static dyn Solution> {
+    solutions_for_year(year).max_by_key(|s| s.day())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{find_solution, latest_solution, solutions_for_year, SOLUTIONS};
+
+    #[test]
+    fn test_solutions_are_in_year_and_day_order() {
+        assert!(SOLUTIONS
+            .windows(2)
+            .all(|w| (w[0].year(), w[0].day()) < (w[1].year(), w[1].day())));
+    }
+
+    #[test]
+    fn test_find_solution() {
+        assert_eq!(
+            Some((2023, 3)),
+            find_solution(2023, 3).map(|s| (s.year(), s.day()))
+        );
+        assert!(find_solution(2023, 0).is_none());
+        assert!(find_solution(2023, 26).is_none());
+        assert!(find_solution(2015, 3).is_none());
+    }
+
+    #[test]
+    fn test_solutions_for_year() {
+        assert!(solutions_for_year(2023).all(|s| s.year() == 2023));
+        assert_eq!(Some(1), solutions_for_year(2023).next().map(|s| s.day()));
+        assert_eq!(0, solutions_for_year(2015).count());
+    }
 
-pub fn parse_file_input(day: u16) -> String {
-    fs::read_to_string(format!("./data/day{:02}.txt", day)).unwrap()
+    #[test]
+    fn test_latest_solution() {
+        let last_2023 = SOLUTIONS.iter().rfind(|s| s.year() == 2023);
+        assert_eq!(
+            last_2023.map(|s| s.day()),
+            latest_solution(2023).map(|s| s.day())
+        );
+        assert!(latest_solution(2015).is_none());
+    }
 }
@@ -1,26 +1,469 @@
-use aoc2023::day01;
-use aoc2023::day02;
-use aoc2023::day03;
-use aoc2023::day04;
-use aoc2023::day05;
-use aoc2023::day06;
-use aoc2023::day07;
-use aoc2023::day08;
-
-fn main() {
-    let day = std::env::args().nth(1).unwrap_or_default();
-    match day.as_str() {
-        "1" => day01::run(),
-        "2" => day02::run(),
-        "3" => day03::run(),
-        "4" => day04::run(),
-        "5" => day05::run(),
-        "6" => day06::run(),
-        "7" => day07::run(),
-        "8" => day08::run(),
-        _ => {
-            println!("No match for day argument '{day}' found. Running most recent day.");
-            day08::run();
+use std::{
+    collections::BTreeMap,
+    fmt,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    thread,
+    time::Instant,
+};
+
+use aoc2023::{
+    answers::{verify, KnownAnswers, Verification},
+    bench::{bench, bench_implementations, BenchRecord, Stage},
+    cli::{Cli, Command, Format, USAGE},
+    client::{fetch_input_cached, Client},
+    find_solution,
+    fixtures::{extract_examples, fixtures_dir, write_examples},
+    input::{answers_path, input_path, ledger_path, InputSource},
+    latest_solution,
+    ledger::Ledger,
+    rng::Rng,
+    runner::{run_day, run_day_with, run_days_parallel, to_json},
+    scaffold::{module_dir, scaffold},
+    solutions_for_year,
+    trace::{self, Level},
+    watch::{changed_files, check, diff_answers, modified_times, POLL_INTERVAL},
+    AocError,
+};
+
+fn main() -> ExitCode {
+    let result = Cli::parse(std::env::args().skip(1)).and_then(
+        |Cli {
+             year,
+             trace,
+             command,
+         }| {
+            if let Some(filter) = trace {
+                trace::init(filter, print_trace);
+            }
+            match command {
+                Command::Run {
+                    day,
+                    input,
+                    format,
+                    implementation,
+                } => run(year, day, input, format, implementation),
+                Command::RunAll { days, format, jobs } => run_all(year, days, format, jobs),
+                Command::Fetch { day } => fetch(year, day),
+                Command::Submit { day, part } => submit(year, day, part),
+                Command::New { day } => new_day(year, day),
+                Command::Examples { day, page } => save_examples(year, day, &page),
+                Command::Verify => verify_all(year),
+                Command::Watch { day } => watch_day(year, day),
+                Command::Generate { day, seed, size } => generate(year, day, seed, size),
+                Command::Render {
+                    day,
+                    input,
+                    rows,
+                    cols,
+                } => render(year, day, input, rows, cols),
+                Command::Bench {
+                    day,
+                    input,
+                    iterations,
+                    warmup,
+                    save,
+                    compare,
+                } => bench_day(year, day, input, iterations, warmup, save, compare),
+            }
+        },
+    );
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e @ AocError::Usage(_)) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn print_trace(day: u16, level: Level, message: fmt::Arguments) {
+    eprintln!("[day{day:02} {level}] {message}");
+}
+
+fn run(
+    year: u16,
+    day: Option<String>,
+    input: Option<InputSource>,
+    format: Format,
+    implementation: Option<String>,
+) -> Result<(), AocError> {
+    let day = day.unwrap_or_default();
+    let solution = match day.parse().ok().and_then(|day| find_solution(year, day)) {
+        Some(solution) => solution,
+        None => {
+            let message =
+                format!("No match for day argument '{day}' found. Running most recent day.");
+            match format {
+                Format::Text => println!("{message}"),
+                Format::Json => eprintln!("{message}"),
+            }
+            latest_solution(year)
+                .ok_or_else(|| AocError::Usage(format!("no days of {year} have been solved")))?
         }
+    };
+
+    let implementation = implementation.as_deref();
+    if let Some(name) = implementation {
+        let mut names: Vec<&str> = [1, 2]
+            .into_iter()
+            .flat_map(|part| solution.implementations(part))
+            .collect();
+        if !names.contains(&name) {
+            names.sort();
+            names.dedup();
+            return Err(AocError::Usage(match names.is_empty() {
+                true => format!(
+                    "day {} has only one implementation of each part",
+                    solution.day()
+                ),
+                false => format!(
+                    "day {} has no implementation called '{name}', expected one of {}",
+                    solution.day(),
+                    names.join(", ")
+                ),
+            }));
+        }
+    }
+
+    let input = input
+        .unwrap_or(InputSource::DataDir {
+            year,
+            day: solution.day(),
+        })
+        .read();
+    if format == Format::Json {
+        let reports = [run_day_with(solution, input, implementation)];
+        println!("{}", to_json(&reports));
+        let [report] = reports;
+        for part in report.parts? {
+            part.answer?;
+        }
+        return Ok(());
+    }
+    let input = input?;
+    println!("Running Day {}: {}", solution.day(), solution.title());
+
+    let report = run_day_with(solution, Ok(input), implementation);
+    let parts = report.parts?;
+    println!("Parsed input in {:?}", report.parse_time);
+    for (question, part) in solution.questions().into_iter().zip(parts) {
+        let label = match implementation {
+            Some(name) if solution.implementations(part.part).contains(&name) => {
+                format!(" ({name})")
+            }
+            _ => String::new(),
+        };
+        println!(
+            "Part {}{label} :: {question} --> {:?} ({:?})",
+            part.part, part.answer?, part.elapsed
+        );
+    }
+    Ok(())
+}
+
+fn run_all(
+    year: u16,
+    days: RangeInclusive<u16>,
+    format: Format,
+    jobs: Option<usize>,
+) -> Result<(), AocError> {
+    let start = Instant::now();
+    let days: Vec<_> = solutions_for_year(year)
+        .filter(|solution| days.contains(&solution.day()))
+        .map(|solution| {
+            let day = solution.day();
+            (solution, InputSource::DataDir { year, day }.read())
+        })
+        .collect();
+    let reports = match jobs {
+        Some(threads) => run_days_parallel(days, threads),
+        None => days
+            .into_iter()
+            .map(|(solution, input)| run_day(solution, input))
+            .collect(),
+    };
+    let total_time = start.elapsed();
+    let failures = reports.iter().filter(|r| r.error().is_some()).count();
+    let result = match failures {
+        0 => Ok(()),
+        _ => Err(AocError::Failed(failures)),
+    };
+    if format == Format::Json {
+        println!("{}", to_json(&reports));
+        return result;
+    }
+
+    let title_width = reports.iter().map(|r| r.title.len()).max().unwrap_or(0);
+    println!(
+        "{:>3}  {:<title_width$}  {:>16}  {:>16}  {:>12}",
+        "Day", "Title", "Part 1", "Part 2", "Time"
+    );
+    for report in &reports {
+        let answers = match &report.parts {
+            Ok(parts) => parts.each_ref().map(|part| match &part.answer {
+                Ok(answer) => answer.to_string(),
+                Err(_) => String::from("error"),
+            }),
+            Err(_) => [(); 2].map(|_| String::from("error")),
+        };
+        println!(
+            "{:>3}  {:<title_width$}  {:>16}  {:>16}  {:>12?}",
+            report.day,
+            report.title,
+            answers[0],
+            answers[1],
+            report.elapsed()
+        );
+    }
+    println!("{:<w$}  {:>12?}", "Total", total_time, w = title_width + 43);
+
+    for report in &reports {
+        if let Some(e) = report.error() {
+            eprintln!("Day {}: {e}", report.day);
+        }
+    }
+    result
+}
+
+fn fetch(year: u16, day: u16) -> Result<(), AocError> {
+    let path = input_path(year, day);
+    if path.exists() {
+        println!(
+            "Input for day {day} is already cached at {}",
+            path.display()
+        );
+        return Ok(());
+    }
+    fetch_input_cached(&Client::from_env()?, year, day, &path)?;
+    println!("Downloaded input for day {day} to {}", path.display());
+    Ok(())
+}
+
+fn submit(year: u16, day: u16, part: u8) -> Result<(), AocError> {
+    let solution = find_solution(year, day)
+        .ok_or_else(|| AocError::Usage(format!("day {day} of {year} has not been solved")))?;
+    let parsed = solution.parse(&InputSource::DataDir { year, day }.read()?)?;
+    let answer = match part {
+        1 => solution.part1(&parsed)?,
+        _ => solution.part2(&parsed)?,
+    };
+
+    println!("Submitting {answer} for day {day} part {part}");
+    let outcome =
+        Ledger::load(&ledger_path(year))?.submit(&Client::from_env()?, year, day, part, answer)?;
+    println!("Outcome: {outcome}");
+    Ok(())
+}
+
+fn new_day(year: u16, day: u16) -> Result<(), AocError> {
+    for path in scaffold(Path::new("src"), &input_path(year, day), year, day)? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn save_examples(year: u16, day: u16, page: &Path) -> Result<(), AocError> {
+    let html = InputSource::Path(page.to_path_buf()).read()?;
+    let examples = extract_examples(&html);
+    if examples.is_empty() {
+        return Err(AocError::Config(format!(
+            "no examples found in '{}'",
+            page.display()
+        )));
+    }
+    for path in write_examples(&fixtures_dir(Path::new("."), year, day), &examples)? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn watch_day(year: u16, day: u16) -> Result<(), AocError> {
+    if find_solution(year, day).is_none() {
+        return Err(AocError::Usage(format!(
+            "day {day} of {year} has not been solved"
+        )));
+    }
+    let paths = [
+        module_dir(Path::new("src"), year, day),
+        input_path(year, day),
+        fixtures_dir(Path::new("."), year, day),
+    ];
+    let mut previous = None;
+    let mut seen = BTreeMap::new();
+    loop {
+        let times = modified_times(&paths);
+        if previous.is_none() || times != seen {
+            if previous.is_some() {
+                for path in changed_files(&seen, &times) {
+                    println!("Changed: {}", path.display());
+                }
+            }
+            seen = times;
+
+            let check = check(year, day)?;
+            for line in diff_answers(previous.as_ref(), &check.answers) {
+                println!("{line}");
+            }
+            for line in &check.messages {
+                println!("{line}");
+            }
+            previous = Some(check.answers);
+            println!("Watching {} paths for changes...", paths.len());
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn generate(year: u16, day: u16, seed: u64, size: usize) -> Result<(), AocError> {
+    let solution = find_solution(year, day)
+        .ok_or_else(|| AocError::Usage(format!("day {day} of {year} has not been solved")))?;
+    let input = solution
+        .generate(&mut Rng::new(seed), size)
+        .ok_or_else(|| AocError::Usage(format!("day {day} of {year} has no input generator")))?;
+    print!("{input}");
+    Ok(())
+}
+
+fn render(
+    year: u16,
+    day: u16,
+    input: Option<InputSource>,
+    rows: RangeInclusive<usize>,
+    cols: RangeInclusive<usize>,
+) -> Result<(), AocError> {
+    let solution = find_solution(year, day)
+        .ok_or_else(|| AocError::Usage(format!("day {day} of {year} has not been solved")))?;
+    let parsed = solution.parse(&input.unwrap_or(InputSource::DataDir { year, day }).read()?)?;
+    let rendered = solution
+        .render(&parsed, rows, cols)
+        .ok_or_else(|| AocError::Usage(format!("day {day} of {year} can't be rendered")))?;
+    print!("{rendered}");
+    Ok(())
+}
+
+fn verify_all(year: u16) -> Result<(), AocError> {
+    let known_answers = KnownAnswers::load(&answers_path(year))?;
+    let mut regressions = 0;
+    for solution in solutions_for_year(year) {
+        let day = solution.day();
+        let verifications = match (InputSource::DataDir { year, day }).read() {
+            Ok(input) => verify(solution, &input, &known_answers),
+            Err(e)
+                if known_answers.get(day, 1).is_none() && known_answers.get(day, 2).is_none() =>
+            {
+                println!("Day {day}: skipped, {e}");
+                continue;
+            }
+            Err(e) => [(); 2].map(|_| Verification::Failed(e.to_string())),
+        };
+        for (part, verification) in (1..).zip(&verifications) {
+            let status = match verification {
+                Verification::Correct(answer) => format!("ok ({answer})"),
+                Verification::Incorrect { expected, actual } => {
+                    format!("REGRESSION, expected {expected} but got {actual}")
+                }
+                Verification::Unknown(answer) => format!("no known answer ({answer})"),
+                Verification::Failed(e) => format!("FAILED, {e}"),
+            };
+            println!("Day {day} part {part}: {status}");
+        }
+        regressions += verifications.iter().filter(|v| v.is_regression()).count();
+    }
+
+    match regressions {
+        0 => Ok(()),
+        _ => Err(AocError::Regression(regressions)),
+    }
+}
+
+fn bench_day(
+    year: u16,
+    day: u16,
+    input: Option<InputSource>,
+    iterations: usize,
+    warmup: usize,
+    save: Option<PathBuf>,
+    compare: Option<PathBuf>,
+) -> Result<(), AocError> {
+    let solution = find_solution(year, day)
+        .ok_or_else(|| AocError::Usage(format!("day {day} of {year} has not been solved")))?;
+    let input = input.unwrap_or(InputSource::DataDir { year, day }).read()?;
+    let baseline = compare.map(|path| BenchRecord::load(&path)).transpose()?;
+
+    println!(
+        "Benchmarking Day {day}: {} ({iterations} iterations, {warmup} warmup)",
+        solution.title()
+    );
+    let stats = bench(solution, &input, warmup, iterations)?;
+    println!(
+        "{:<6} {:>12} {:>12} {:>12} {:>12}",
+        "stage", "min", "median", "mean", "stddev"
+    );
+    let mut regressions = 0;
+    for (stage, stage_stats) in Stage::ALL.into_iter().zip(&stats) {
+        let flag = match baseline.as_ref().and_then(|b| b.get(year, day, stage)) {
+            Some(saved) if stage_stats.is_regression_from(saved) => {
+                regressions += 1;
+                format!("  SLOWER, saved median {:?}", saved.median)
+            }
+            _ => String::new(),
+        };
+        println!(
+            "{stage:<6} {:>12?} {:>12?} {:>12?} {:>12?}{flag}",
+            stage_stats.min, stage_stats.median, stage_stats.mean, stage_stats.stddev
+        );
+    }
+
+    let implementations = bench_implementations(solution, &input, warmup, iterations)?;
+    if !implementations.is_empty() {
+        let name_width = implementations
+            .iter()
+            .map(|(_, name, _)| name.len())
+            .max()
+            .unwrap_or(0)
+            .max("implementation".len());
+        println!(
+            "\n{:<6} {:<name_width$} {:>12} {:>12} {:>12} {:>12} {:>10}",
+            "stage", "implementation", "min", "median", "mean", "stddev", "vs fastest"
+        );
+        for (part, name, stats) in &implementations {
+            // Compare each implementation to the fastest of the same part
+            let fastest = implementations
+                .iter()
+                .filter(|(other, _, _)| other == part)
+                .map(|(_, _, stats)| stats.median)
+                .min()
+                .unwrap_or(stats.median);
+            let ratio = stats.median.as_secs_f64() / fastest.as_secs_f64().max(f64::MIN_POSITIVE);
+            println!(
+                "{:<6} {name:<name_width$} {:>12?} {:>12?} {:>12?} {:>12?} {:>9.2}x",
+                format!("part{part}"),
+                stats.min,
+                stats.median,
+                stats.mean,
+                stats.stddev,
+                ratio
+            );
+        }
+    }
+
+    if let Some(path) = save {
+        let mut record = BenchRecord::load(&path)?;
+        for (stage, stage_stats) in Stage::ALL.into_iter().zip(stats) {
+            record.insert(year, day, stage, stage_stats);
+        }
+        record.save()?;
+        println!("Saved results to {}", path.display());
+    }
+
+    match regressions {
+        0 => Ok(()),
+        _ => Err(AocError::PerformanceRegression(regressions)),
     }
 }
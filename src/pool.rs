@@ -0,0 +1,109 @@
+use std::{
+    sync::{mpsc, Arc, Mutex},
+    thread::{self, JoinHandle},
+};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// A fixed number of worker threads that run jobs in the order they were queued.
+pub struct ThreadPool {
+    spawner: Option<Spawner>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+/// A handle for queueing jobs on a pool, which jobs can hold to queue more jobs.
+#[derive(Clone)]
+pub struct Spawner(mpsc::Sender<Job>);
+
+impl ThreadPool {
+    /// Start a pool of `threads` workers, which must be at least one.
+    pub fn new(threads: usize) -> ThreadPool {
+        assert!(threads > 0, "A thread pool needs at least one thread");
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..threads)
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || loop {
+                    // The lock is released before the job runs, so other workers can take jobs
+                    let job = receiver.lock().unwrap().recv();
+                    match job {
+                        Ok(job) => job(),
+                        // Every spawner is gone, so no more jobs can arrive
+                        Err(_) => break,
+                    }
+                })
+            })
+            .collect();
+        ThreadPool {
+            spawner: Some(Spawner(sender)),
+            workers,
+        }
+    }
+
+    pub fn spawner(&self) -> Spawner {
+        self.spawner.clone().unwrap()
+    }
+
+    pub fn execute(&self, job: impl FnOnce() + Send + 'static) {
+        self.spawner().execute(job);
+    }
+}
+
+impl Spawner {
+    pub fn execute(&self, job: impl FnOnce() + Send + 'static) {
+        // Workers only stop once every spawner is dropped, so the channel is still open
+        self.0.send(Box::new(job)).unwrap();
+    }
+}
+
+impl Drop for ThreadPool {
+    /// Wait for every queued job, including any they queue, to finish.
+    fn drop(&mut self) {
+        drop(self.spawner.take());
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use crate::pool::ThreadPool;
+
+    #[test]
+    fn test_execute() {
+        let (sender, receiver) = mpsc::channel();
+        let pool = ThreadPool::new(3);
+        for i in 0..10 {
+            let sender = sender.clone();
+            pool.execute(move || sender.send(i).unwrap());
+        }
+        drop(sender);
+        drop(pool);
+
+        let mut results: Vec<i32> = receiver.iter().collect();
+        results.sort();
+        assert_eq!((0..10).collect::<Vec<_>>(), results);
+    }
+
+    #[test]
+    fn test_jobs_can_queue_jobs() {
+        let (sender, receiver) = mpsc::channel();
+        let pool = ThreadPool::new(2);
+        let spawner = pool.spawner();
+        pool.execute(move || {
+            for i in 0..3 {
+                let sender = sender.clone();
+                spawner.execute(move || sender.send(i).unwrap());
+            }
+        });
+        drop(pool);
+
+        let mut results: Vec<i32> = receiver.iter().collect();
+        results.sort();
+        assert_eq!(vec![0, 1, 2], results);
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt::Debug;
+
+use crate::rng::Rng;
+
+/// How many random values each property is checked against.
+const CASES: usize = 500;
+
+/// The seed of the values checked, fixed so that a failure can be reproduced.
+const SEED: u64 = 2023;
+
+/// Check that `property` holds for random values from `generate`. If it doesn't, the failing
+/// value is shrunk to the simplest value that still fails, and the test panics with it.
+pub(crate) fn check<T: Debug>(
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    property: impl Fn(&T) -> bool,
+) {
+    if let Some((value, shrinks)) = counterexample(generate, shrink, property) {
+        panic!("Property does not hold for {value:?} (shrunk {shrinks} times)");
+    }
+}
+
+/// The simplest value found that `property` doesn't hold for, and how many times it was shrunk,
+/// if there is one among the values checked.
+pub(crate) fn counterexample<T>(
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    property: impl Fn(&T) -> bool,
+) -> Option<(T, usize)> {
+    let mut rng = Rng::new(SEED);
+    let mut value = (0..CASES)
+        .map(|_| generate(&mut rng))
+        .find(|value| !property(value))?;
+    // Move to the first simpler value that still fails until none of them do
+    let mut shrinks = 0;
+    while let Some(simpler) = shrink(&value).into_iter().find(|v| !property(v)) {
+        value = simpler;
+        shrinks += 1;
+    }
+    Some((value, shrinks))
+}
+
+/// Simpler values for a number: zero, half of it and one less.
+pub(crate) fn shrink_usize(n: usize) -> Vec<usize> {
+    let mut simpler = Vec::new();
+    for candidate in [0, n / 2, n.saturating_sub(1)] {
+        if candidate < n && !simpler.contains(&candidate) {
+            simpler.push(candidate);
+        }
+    }
+    simpler
+}
+
+/// Simpler values for a list: each half of it, it without each item, and it with each item
+/// replaced by a simpler one from `shrink_item`.
+pub(crate) fn shrink_vec<T: Clone>(items: &[T], shrink_item: impl Fn(&T) -> Vec<T>) -> Vec<Vec<T>> {
+    let mut simpler = Vec::new();
+    if items.len() > 1 {
+        let (first, second) = items.split_at(items.len() / 2);
+        simpler.extend([first.to_vec(), second.to_vec()]);
+    }
+    for i in 0..items.len() {
+        let mut fewer = items.to_vec();
+        fewer.remove(i);
+        simpler.push(fewer);
+    }
+    for (i, item) in items.iter().enumerate() {
+        for item in shrink_item(item) {
+            let mut changed = items.to_vec();
+            changed[i] = item;
+            simpler.push(changed);
+        }
+    }
+    simpler
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prop::{counterexample, shrink_usize, shrink_vec};
+
+    #[test]
+    fn test_counterexample_is_shrunk() {
+        let found = counterexample(
+            |rng| rng.range(0..=1000),
+            |&n| shrink_usize(n),
+            |&n| n < 500,
+        );
+        assert_eq!(Some(500), found.map(|(n, _)| n));
+
+        let found = counterexample(
+            |rng| {
+                (0..rng.range(0..=10))
+                    .map(|_| rng.range(0..=100))
+                    .collect::<Vec<_>>()
+            },
+            |items| shrink_vec(items, |&n| shrink_usize(n)),
+            |items| !items.contains(&7),
+        );
+        assert_eq!(Some(vec![7]), found.map(|(items, _)| items));
+    }
+
+    #[test]
+    fn test_no_counterexample() {
+        assert!(counterexample(
+            |rng| rng.range(0..=1000),
+            |&n| shrink_usize(n),
+            |&n| n <= 1000
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_shrink() {
+        assert_eq!(vec![0, 5, 9], shrink_usize(10));
+        assert_eq!(vec![0], shrink_usize(1));
+        assert!(shrink_usize(0).is_empty());
+        assert_eq!(
+            vec![
+                vec![1],
+                vec![2],
+                vec![2],
+                vec![1],
+                vec![0, 2],
+                vec![1, 0],
+                vec![1, 1]
+            ],
+            shrink_vec(&[1, 2], |&n| shrink_usize(n))
+        );
+    }
+}
@@ -0,0 +1,346 @@
+use std::{
+    sync::{mpsc, Arc},
+    time::{Duration, Instant},
+};
+
+use crate::{pool::ThreadPool, AocError, Solution};
+
+/// The answer to one part and how long it took to solve.
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Result<usize, AocError>,
+    pub elapsed: Duration,
+}
+
+/// The outcome of running both parts of a day.
+#[derive(Debug)]
+pub struct DayReport {
+    pub year: u16,
+    pub day: u16,
+    pub title: &'static str,
+    pub parse_time: Duration,
+    /// The report for each part, or the error reading or parsing the input
+    pub parts: Result<[PartReport; 2], AocError>,
+}
+
+impl DayReport {
+    /// The time taken to parse the input and solve both parts.
+    pub fn elapsed(&self) -> Duration {
+        let parts_time = match &self.parts {
+            Ok(parts) => parts.iter().map(|part| part.elapsed).sum(),
+            Err(_) => Duration::ZERO,
+        };
+        self.parse_time + parts_time
+    }
+
+    /// The first error in reading, parsing or solving, if any.
+    pub fn error(&self) -> Option<&AocError> {
+        match &self.parts {
+            Ok(parts) => parts.iter().find_map(|part| part.answer.as_ref().err()),
+            Err(e) => Some(e),
+        }
+    }
+}
+
+/// Format reports as a JSON array with a record for each part of each day, like
+/// `{"year": 2023, "day": 6, "part": 1, "answer": 288, "duration_ns": 1500, "error": null}`.
+/// An error reading or parsing a day's input is reported against both parts.
+pub fn to_json(reports: &[DayReport]) -> String {
+    let records: Vec<String> = reports
+        .iter()
+        .flat_map(|report| match &report.parts {
+            Ok(parts) => parts
+                .each_ref()
+                .map(|part| json_record(report, part.part, part.answer.as_ref(), part.elapsed)),
+            Err(e) => [1, 2].map(|part| json_record(report, part, Err(e), Duration::ZERO)),
+        })
+        .collect();
+    match records.is_empty() {
+        true => String::from("[]"),
+        false => format!("[\n  {}\n]", records.join(",\n  ")),
+    }
+}
+
+fn json_record(
+    report: &DayReport,
+    part: u8,
+    answer: Result<&usize, &AocError>,
+    elapsed: Duration,
+) -> String {
+    let (answer, error) = match answer {
+        Ok(answer) => (answer.to_string(), String::from("null")),
+        Err(e) => (String::from("null"), json_string(&e.to_string())),
+    };
+    format!(
+        "{{\"year\": {}, \"day\": {}, \"part\": {part}, \"answer\": {answer}, \"duration_ns\": {}, \"error\": {error}}}",
+        report.year,
+        report.day,
+        elapsed.as_nanos()
+    )
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Parse the input and solve both parts of `solution`, timing each step.
+pub fn run_day(solution: &dyn Solution, input: Result<String, AocError>) -> DayReport {
+    run_day_with(solution, input, None)
+}
+
+/// Like [`run_day`], but solving each part that has an implementation called `implementation`
+/// with it instead of the default.
+pub fn run_day_with(
+    solution: &dyn Solution,
+    input: Result<String, AocError>,
+    implementation: Option<&str>,
+) -> DayReport {
+    let start = Instant::now();
+    let parsed = input.and_then(|input| solution.parse(&input));
+    let parse_time = start.elapsed();
+    DayReport {
+        year: solution.year(),
+        day: solution.day(),
+        title: solution.title(),
+        parse_time,
+        parts: parsed.map(|parsed| {
+            [1, 2].map(|part| {
+                let start = Instant::now();
+                let answer = implementation
+                    .and_then(|name| solution.solve_with(part, name, &parsed))
+                    .unwrap_or_else(|| match part {
+                        1 => solution.part1(&parsed),
+                        _ => solution.part2(&parsed),
+                    });
+                PartReport {
+                    part,
+                    answer,
+                    elapsed: start.elapsed(),
+                }
+            })
+        }),
+    }
+}
+
+/// Progress reported by a job run on the pool by [`run_days_parallel`].
+enum Message {
+    Parsed {
+        index: usize,
+        parse_time: Duration,
+        error: Option<AocError>,
+    },
+    Solved {
+        index: usize,
+        report: PartReport,
+    },
+}
+
+/// Run each day like [`run_day`], but on a pool of `threads` workers, so that days and the two
+/// parts of each day run concurrently once their input is parsed. The reports are in the same
+/// order as `days`, however the jobs finish.
+pub fn run_days_parallel(
+    days: Vec<(&'static dyn Solution, Result<String, AocError>)>,
+    threads: usize,
+) -> Vec<DayReport> {
+    let mut reports: Vec<DayReport> = days
+        .iter()
+        .map(|(solution, _)| DayReport {
+            year: solution.year(),
+            day: solution.day(),
+            title: solution.title(),
+            parse_time: Duration::ZERO,
+            // Replaced once the day's jobs finish, which they only fail to if one panics
+            parts: Err(AocError::Unfinished(solution.day())),
+        })
+        .collect();
+    let mut parts: Vec<[Option<PartReport>; 2]> = days.iter().map(|_| [None, None]).collect();
+
+    let (sender, receiver) = mpsc::channel();
+    let pool = ThreadPool::new(threads);
+    for (index, (solution, input)) in days.into_iter().enumerate() {
+        let sender = sender.clone();
+        let spawner = pool.spawner();
+        pool.execute(move || {
+            let start = Instant::now();
+            let parsed = input.and_then(|input| solution.parse(&input));
+            let parse_time = start.elapsed();
+            let parsed = match parsed {
+                Ok(parsed) => Arc::new(parsed),
+                Err(e) => {
+                    let _ = sender.send(Message::Parsed {
+                        index,
+                        parse_time,
+                        error: Some(e),
+                    });
+                    return;
+                }
+            };
+            for part in [1, 2] {
+                let sender = sender.clone();
+                let parsed = Arc::clone(&parsed);
+                spawner.execute(move || {
+                    let start = Instant::now();
+                    let answer = match part {
+                        1 => solution.part1(&parsed),
+                        _ => solution.part2(&parsed),
+                    };
+                    let report = PartReport {
+                        part,
+                        answer,
+                        elapsed: start.elapsed(),
+                    };
+                    let _ = sender.send(Message::Solved { index, report });
+                });
+            }
+            let _ = sender.send(Message::Parsed {
+                index,
+                parse_time,
+                error: None,
+            });
+        });
+    }
+    // Only the jobs hold senders now, so receiving ends once every job has finished
+    drop(sender);
+
+    for message in receiver {
+        match message {
+            Message::Parsed {
+                index,
+                parse_time,
+                error,
+            } => {
+                reports[index].parse_time = parse_time;
+                if let Some(e) = error {
+                    reports[index].parts = Err(e);
+                }
+            }
+            Message::Solved { index, report } => {
+                let slot = usize::from(report.part - 1);
+                parts[index][slot] = Some(report);
+            }
+        }
+    }
+    drop(pool);
+
+    for (report, parts) in reports.iter_mut().zip(parts) {
+        if let [Some(part1), Some(part2)] = parts {
+            report.parts = Ok([part1, part2]);
+        }
+    }
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::day06::Day06;
+    use crate::fixtures::examples;
+    use crate::runner::{json_string, run_day, run_day_with, run_days_parallel, to_json};
+    use crate::{find_solution, AocError, Puzzle};
+
+    #[test]
+    fn test_run_day() {
+        let (input, _) = &examples(Day06::YEAR, Day06::DAY, 1)[0];
+        let report = run_day(&Day06, Ok(input.clone()));
+        assert_eq!(6, report.day);
+        assert_eq!("Wait For It", report.title);
+        let [part1, part2] = report.parts.as_ref().unwrap();
+        assert_eq!((1, 288), (part1.part, *part1.answer.as_ref().unwrap()));
+        assert_eq!((2, 71503), (part2.part, *part2.answer.as_ref().unwrap()));
+        assert!(report.error().is_none());
+        assert!(report.elapsed() >= part1.elapsed + part2.elapsed);
+    }
+
+    #[test]
+    fn test_run_day_with() {
+        let (input, _) = &examples(Day06::YEAR, Day06::DAY, 1)[0];
+        for implementation in [Some("brute"), Some("closed-form"), Some("unknown"), None] {
+            let report = run_day_with(&Day06, Ok(input.clone()), implementation);
+            let answers = report.parts.unwrap().map(|part| part.answer.unwrap());
+            assert_eq!([288, 71503], answers);
+        }
+    }
+
+    #[test]
+    fn test_run_day_errors() {
+        let report = run_day(&Day06, Ok(String::from("Time: 7 15 30")));
+        assert!(matches!(
+            report.error(),
+            Some(AocError::Malformed { day: 6, .. })
+        ));
+
+        let report = run_day(&Day06, Err(AocError::Config(String::from("no input"))));
+        assert!(matches!(report.error(), Some(AocError::Config(_))));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let (input, _) = &examples(Day06::YEAR, Day06::DAY, 1)[0];
+        let mut reports = vec![
+            run_day(&Day06, Ok(input.clone())),
+            run_day(&Day06, Err(AocError::Config(String::from("no \"input\"")))),
+        ];
+        for part in reports[0].parts.as_mut().unwrap() {
+            part.elapsed = Duration::from_nanos(1500);
+        }
+        assert_eq!(
+            r#"[
+  {"year": 2023, "day": 6, "part": 1, "answer": 288, "duration_ns": 1500, "error": null},
+  {"year": 2023, "day": 6, "part": 2, "answer": 71503, "duration_ns": 1500, "error": null},
+  {"year": 2023, "day": 6, "part": 1, "answer": null, "duration_ns": 0, "error": "no \"input\""},
+  {"year": 2023, "day": 6, "part": 2, "answer": null, "duration_ns": 0, "error": "no \"input\""}
+]"#,
+            to_json(&reports)
+        );
+        assert_eq!("[]", to_json(&[]));
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(r#""a\\b\nc\u0007""#, json_string("a\\b\nc\u{7}"));
+    }
+
+    #[test]
+    fn test_run_days_parallel() {
+        let day = |day| find_solution(2023, day).unwrap();
+        let (input, _) = &examples(Day06::YEAR, Day06::DAY, 1)[0];
+        let days = vec![
+            (day(6), Ok(input.clone())),
+            (day(1), Ok(String::from("two1nine\n7pqrstsixteen"))),
+            (day(6), Ok(String::from("Time: 7 15 30"))),
+            (day(2), Err(AocError::Config(String::from("no input")))),
+        ];
+        let reports = run_days_parallel(days, 3);
+
+        assert_eq!(
+            vec![6, 1, 6, 2],
+            reports.iter().map(|r| r.day).collect::<Vec<_>>()
+        );
+        let answers = |index: usize| {
+            let parts = reports[index].parts.as_ref().unwrap();
+            parts.each_ref().map(|part| *part.answer.as_ref().unwrap())
+        };
+        assert_eq!([288, 71503], answers(0));
+        assert_eq!([88, 105], answers(1));
+        assert!(matches!(
+            reports[2].error(),
+            Some(AocError::Malformed { day: 6, .. })
+        ));
+        assert!(matches!(reports[3].error(), Some(AocError::Config(_))));
+    }
+}
@@ -0,0 +1,419 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{AocError, DEFAULT_YEAR};
+
+/// The module generated for a new day, with `__IMPORTS__` standing for the imports of its tests,
+/// `__NN__` for the zero padded day, and `__YEAR__` and `__DAY__` for the year and day.
+const TEMPLATE: &str = r#"use crate::{Answers, AocError, Puzzle};
+
+pub struct Day__NN__;
+
+impl Puzzle for Day__NN__ {
+    const YEAR: u16 = __YEAR__;
+    const DAY: u16 = __DAY__;
+    const TITLE: &'static str = "TODO";
+    const QUESTIONS: [&'static str; 2] = ["TODO", "TODO"];
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        part2(input)
+    }
+}
+
+/// Solve both parts of the puzzle for `input`.
+pub fn solve(input: &str) -> Result<Answers, AocError> {
+    Day__NN__::solve(input)
+}
+
+fn part1(_lines: &[String]) -> Result<usize, AocError> {
+    Err(AocError::MissingAnswer {
+        day: Day__NN__::DAY,
+        part: 1,
+        reason: String::from("not solved yet"),
+    })
+}
+
+fn part2(_lines: &[String]) -> Result<usize, AocError> {
+    Err(AocError::MissingAnswer {
+        day: Day__NN__::DAY,
+        part: 2,
+        reason: String::from("not solved yet"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    __IMPORTS__
+    use crate::Puzzle;
+
+    #[test]
+    #[ignore = "needs fixtures saved with the examples command"]
+    fn test_part1_sample_input() {
+        for (input, answer) in examples(Day__NN__::YEAR, Day__NN__::DAY, 1) {
+            assert_eq!(answer, part1(&Day__NN__::parse(&input).unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    #[ignore = "needs fixtures saved with the examples command"]
+    fn test_part2_sample_input() {
+        for (input, answer) in examples(Day__NN__::YEAR, Day__NN__::DAY, 2) {
+            assert_eq!(answer, part2(&Day__NN__::parse(&input).unwrap()).unwrap());
+        }
+    }
+}
+"#;
+
+/// The path of the module for a day. Days of the default year are top level modules like
+/// `day09`, and days of other years are in a module for the year like `y2024::day09`.
+pub fn module_path(year: u16, day: u16) -> String {
+    match year {
+        DEFAULT_YEAR => format!("day{day:02}"),
+        _ => format!("y{year}::day{day:02}"),
+    }
+}
+
+/// The directory under `src_dir` that the module for a day is in, following [`module_path`].
+pub fn module_dir(src_dir: &Path, year: u16, day: u16) -> PathBuf {
+    let year_dir = match year {
+        DEFAULT_YEAR => src_dir.to_path_buf(),
+        _ => src_dir.join(format!("y{year}")),
+    };
+    year_dir.join(format!("day{day:02}"))
+}
+
+/// The year and day of a module path returned by [`module_path`].
+fn module_key(path: &str) -> Option<(u16, u16)> {
+    let (year, day) = match path.split_once("::") {
+        Some((year, day)) => (year.strip_prefix('y')?.parse().ok()?, day),
+        None => (DEFAULT_YEAR, path),
+    };
+    Some((year, day.strip_prefix("day")?.parse().ok()?))
+}
+
+/// The name of a module declared in `lib.rs`, which keeps its declarations in name order.
+fn declared_module(line: &str) -> Option<&str> {
+    let line = line.strip_prefix("pub ").unwrap_or(line);
+    line.strip_prefix("mod ")?.strip_suffix(';')
+}
+
+/// The imports of the tests in the module for a new day, in the order rustfmt keeps them in.
+fn test_imports(year: u16, day: u16) -> String {
+    let mut imports = [
+        format!(
+            "use crate::{}::{{part1, part2, Day{day:02}}};",
+            module_path(year, day)
+        ),
+        String::from("use crate::fixtures::examples;"),
+    ];
+    imports.sort();
+    imports.join("\n    ")
+}
+
+/// The source of the module for a new day.
+pub fn module_source(year: u16, day: u16) -> String {
+    TEMPLATE
+        .replace("__IMPORTS__", &test_imports(year, day))
+        .replace("__NN__", &format!("{day:02}"))
+        .replace("__YEAR__", &year.to_string())
+        .replace("__DAY__", &day.to_string())
+}
+
+/// Add a new day to the source of `lib.rs`, keeping `SOLUTIONS` in order of year and day. A day
+/// of the default year is declared as a module here, and for other years the module for the year
+/// is declared if it isn't already.
+pub fn register(lib_source: &str, year: u16, day: u16) -> Result<String, AocError> {
+    let path = module_path(year, day);
+    let solution = format!("    &{path}::Day{day:02},");
+    if lib_source.lines().any(|line| line == solution) {
+        return Err(AocError::Config(format!(
+            "{year} day {day} is already registered in lib.rs"
+        )));
+    }
+    let year_module = format!("pub mod y{year};");
+    let lib_source = match year {
+        DEFAULT_YEAR => insert_in_order(
+            lib_source,
+            &format!("pub mod day{day:02};"),
+            path.as_str(),
+            declared_module,
+        )?,
+        _ if lib_source.lines().any(|line| line == year_module) => lib_source.to_string(),
+        _ => insert_in_order(
+            lib_source,
+            &year_module,
+            format!("y{year}").as_str(),
+            declared_module,
+        )?,
+    };
+    insert_in_order(&lib_source, &solution, (year, day), |line| {
+        let (path, _) = line.strip_prefix("    &")?.rsplit_once("::")?;
+        module_key(path)
+    })
+}
+
+/// Add a new day to the source of the module for its year, which is empty if there isn't one yet.
+pub fn register_in_year(year_source: &str, day: u16) -> Result<String, AocError> {
+    let module = format!("pub mod day{day:02};");
+    if year_source.trim().is_empty() {
+        return Ok(module + "\n");
+    }
+    insert_in_order(year_source, &module, (0, day), |line| {
+        let day = line.strip_prefix("pub mod day")?.strip_suffix(';')?;
+        Some((0, day.parse().ok()?))
+    })
+}
+
+/// Insert `new_line` before the first line that `key_of` finds a greater key in than `key`, and
+/// any attributes on it, or after the last line with a key if there is none.
+fn insert_in_order<'a, K: Ord>(
+    source: &'a str,
+    new_line: &'a str,
+    key: K,
+    key_of: impl Fn(&'a str) -> Option<K>,
+) -> Result<String, AocError> {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let keyed: Vec<(usize, K)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, key_of(line)?)))
+        .collect();
+    let index = match keyed.iter().find(|(_, k)| *k > key) {
+        Some((i, _)) => {
+            let attributes = lines[..*i]
+                .iter()
+                .rev()
+                .take_while(|line| line.starts_with("#["))
+                .count();
+            i - attributes
+        }
+        None => match keyed.last() {
+            Some((i, _)) => i + 1,
+            None => {
+                return Err(AocError::Config(format!(
+                    "could not find where to add '{}'",
+                    new_line.trim()
+                )))
+            }
+        },
+    };
+    lines.insert(index, new_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Generate the module for a new day under `src_dir`, register it, and create an empty input
+/// file at `input_path` if there isn't one. Returns the paths that were written.
+pub fn scaffold(
+    src_dir: &Path,
+    input_path: &Path,
+    year: u16,
+    day: u16,
+) -> Result<Vec<PathBuf>, AocError> {
+    let lib_path = src_dir.join("lib.rs");
+    let module_dir = module_dir(src_dir, year, day);
+    let module_path = module_dir.join("mod.rs");
+    if module_path.exists() {
+        return Err(AocError::Config(format!(
+            "'{}' already exists",
+            module_path.display()
+        )));
+    }
+    let lib_source = register(&read(&lib_path)?, year, day)?;
+    let year_module = match year {
+        DEFAULT_YEAR => None,
+        _ => {
+            let path = src_dir.join(format!("y{year}")).join("mod.rs");
+            let source = match path.exists() {
+                true => read(&path)?,
+                false => String::new(),
+            };
+            Some((path, register_in_year(&source, day)?))
+        }
+    };
+
+    create_dir(&module_dir)?;
+    write(&module_path, &module_source(year, day))?;
+    let mut written = vec![module_path];
+    if let Some((path, source)) = year_module {
+        write(&path, &source)?;
+        written.push(path);
+    }
+    write(&lib_path, &lib_source)?;
+    written.push(lib_path);
+    if !input_path.exists() {
+        if let Some(dir) = input_path.parent() {
+            create_dir(dir)?;
+        }
+        write(input_path, "")?;
+        written.push(input_path.to_path_buf());
+    }
+    Ok(written)
+}
+
+fn read(path: &Path) -> Result<String, AocError> {
+    fs::read_to_string(path).map_err(|source| AocError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), AocError> {
+    fs::write(path, contents).map_err(|source| AocError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn create_dir(dir: &Path) -> Result<(), AocError> {
+    fs::create_dir_all(dir).map_err(|source| AocError::Io {
+        path: dir.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use crate::scaffold::{module_path, module_source, register, register_in_year, scaffold};
+    use crate::AocError;
+
+    const LIB_SOURCE: &str = "pub mod cli;
+pub mod day01;
+pub mod day03;
+mod error;
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day03::Day03,
+];
+";
+
+    #[test]
+    fn test_module_source() {
+        let source = module_source(2023, 9);
+        assert!(source.contains("pub struct Day09;"));
+        assert!(source.contains("    Day09::solve(input)"));
+        assert!(source.contains("const YEAR: u16 = 2023;"));
+        assert!(source.contains("const DAY: u16 = 9;"));
+        assert!(source.contains("use crate::day09::{part1, part2, Day09};"));
+        assert!(!source.contains("__"));
+
+        let source = module_source(2022, 9);
+        assert!(source.contains("const YEAR: u16 = 2022;"));
+        assert!(source.contains("use crate::y2022::day09::{part1, part2, Day09};"));
+    }
+
+    #[test]
+    fn test_module_path() {
+        assert_eq!("day09", module_path(2023, 9));
+        assert_eq!("y2022::day09", module_path(2022, 9));
+    }
+
+    #[test]
+    fn test_register() {
+        assert_eq!(
+            "pub mod cli;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+mod error;
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+];
+",
+            register(LIB_SOURCE, 2023, 2).unwrap()
+        );
+
+        let registered = register(LIB_SOURCE, 2023, 12).unwrap();
+        assert!(registered.contains("pub mod day03;\npub mod day12;\nmod error;"));
+        assert!(registered.contains("    &day03::Day03,\n    &day12::Day12,\n];"));
+    }
+
+    #[test]
+    fn test_register_other_years() {
+        let registered = register(LIB_SOURCE, 2024, 2).unwrap();
+        assert!(registered.contains("mod error;\npub mod y2024;\n\n"));
+        assert!(registered.contains("    &day03::Day03,\n    &y2024::day02::Day02,\n];"));
+
+        let registered = register(&registered, 2022, 5).unwrap();
+        assert!(registered.contains("mod error;\npub mod y2022;\npub mod y2024;\n\n"));
+        assert!(registered.contains(
+            "    &y2022::day05::Day05,\n    &day01::Day01,\n    &day03::Day03,\n    &y2024::day02::Day02,\n"
+        ));
+
+        let registered = register(&registered, 2024, 1).unwrap();
+        assert_eq!(1, registered.matches("pub mod y2024;").count());
+        assert!(registered.contains("    &y2024::day01::Day01,\n    &y2024::day02::Day02,\n"));
+    }
+
+    #[test]
+    fn test_register_in_year() {
+        assert_eq!("pub mod day02;\n", register_in_year("", 2).unwrap());
+        assert_eq!(
+            "pub mod day02;\npub mod day03;\npub mod day07;\n",
+            register_in_year("pub mod day02;\npub mod day07;\n", 3).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_register_errors() {
+        assert!(matches!(
+            register(LIB_SOURCE, 2023, 3),
+            Err(AocError::Config(_))
+        ));
+        assert!(matches!(
+            register("pub mod cli;\n", 2023, 3),
+            Err(AocError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let dir = env::temp_dir().join(format!("aoc2023-scaffold-{}", std::process::id()));
+        let src_dir = dir.join("src");
+        let input_path = dir.join("data").join("2023").join("day02.txt");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("lib.rs"), LIB_SOURCE).unwrap();
+
+        let written = scaffold(&src_dir, &input_path, 2023, 2).unwrap();
+        assert_eq!(3, written.len());
+        assert_eq!(
+            module_source(2023, 2),
+            fs::read_to_string(src_dir.join("day02").join("mod.rs")).unwrap()
+        );
+        assert!(fs::read_to_string(src_dir.join("lib.rs"))
+            .unwrap()
+            .contains("pub mod day02;"));
+        assert_eq!("", fs::read_to_string(&input_path).unwrap());
+
+        assert!(matches!(
+            scaffold(&src_dir, &input_path, 2023, 2),
+            Err(AocError::Config(_))
+        ));
+
+        let input_path = dir.join("data").join("2022").join("day02.txt");
+        assert_eq!(4, scaffold(&src_dir, &input_path, 2022, 2).unwrap().len());
+        assert!(src_dir.join("y2022").join("day02").join("mod.rs").exists());
+        assert_eq!(
+            "pub mod day02;\n",
+            fs::read_to_string(src_dir.join("y2022").join("mod.rs")).unwrap()
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,213 @@
+use std::{any::Any, ops::RangeInclusive};
+
+use crate::{rng::Rng, AocError};
+
+/// A solved Advent of Code puzzle.
+///
+/// Each day implements this trait on a unit struct (e.g. `Day01`). The raw input is parsed
+/// once by [`Puzzle::parse`] and both parts borrow the parsed value.
+pub trait Puzzle {
+    /// The year of the event the puzzle is from.
+    const YEAR: u16;
+
+    /// The day of the event the puzzle was released on, starting at 1.
+    const DAY: u16;
+
+    /// The title of the puzzle, as shown on the puzzle page.
+    const TITLE: &'static str;
+
+    /// The questions asked by part 1 and part 2 of the puzzle.
+    const QUESTIONS: [&'static str; 2];
+
+    /// The parsed form of the puzzle input, shared by both parts.
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError>;
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError>;
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError>;
+
+    /// Named ways of solving each part, for puzzles that have more than one. Each gives the same
+    /// answers as [`Puzzle::part1`] and [`Puzzle::part2`], which use one of them.
+    fn implementations() -> Vec<Implementation<Self::Input>> {
+        Vec::new()
+    }
+
+    /// A random input of about `size` items in the format [`Puzzle::parse`] accepts, for days
+    /// that can generate one.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// The parsed input in `rows` and `cols` annotated for debugging, like with ANSI colours,
+    /// for days that can render one. Rows and columns count from 0.
+    fn render(
+        _input: &Self::Input,
+        _rows: RangeInclusive<usize>,
+        _cols: RangeInclusive<usize>,
+    ) -> Option<String> {
+        None
+    }
+
+    /// Parse `input` and solve both parts of the puzzle.
+    fn solve(input: &str) -> Result<Answers, AocError> {
+        let parsed = Self::parse(input)?;
+        Ok(Answers {
+            part1: Self::part1(&parsed)?,
+            part2: Self::part2(&parsed)?,
+        })
+    }
+}
+
+/// The answers to both parts of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: usize,
+    pub part2: usize,
+}
+
+/// A named way of solving one part of a puzzle, like `brute` or `closed-form`.
+pub struct Implementation<I> {
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: fn(&I) -> Result<usize, AocError>,
+}
+
+impl<I> Implementation<I> {
+    pub fn new(
+        part: u8,
+        name: &'static str,
+        solve: fn(&I) -> Result<usize, AocError>,
+    ) -> Implementation<I> {
+        Implementation { part, name, solve }
+    }
+}
+
+/// A parsed puzzle input whose concrete type is only known to the [`Solution`] that parsed it.
+pub type ParsedInput = Box<dyn Any + Send + Sync>;
+
+/// An object-safe view of a [`Puzzle`], so that days with different `Input` types can be
+/// stored together in [`crate::SOLUTIONS`] and driven by the runner.
+///
+/// This is implemented for every [`Puzzle`] and should not need to be implemented by hand.
+pub trait Solution: Sync {
+    fn year(&self) -> u16;
+
+    fn day(&self) -> u16;
+
+    fn title(&self) -> &'static str;
+
+    fn questions(&self) -> [&'static str; 2];
+
+    fn parse(&self, input: &str) -> Result<ParsedInput, AocError>;
+
+    /// Solve part 1 using an input returned by this solution's [`Solution::parse`].
+    fn part1(&self, input: &ParsedInput) -> Result<usize, AocError>;
+
+    /// Solve part 2 using an input returned by this solution's [`Solution::parse`].
+    fn part2(&self, input: &ParsedInput) -> Result<usize, AocError>;
+
+    /// The names of the implementations of `part` from [`Puzzle::implementations`], if any.
+    fn implementations(&self, part: u8) -> Vec<&'static str>;
+
+    /// Solve `part` with the implementation called `name`, using an input returned by this
+    /// solution's [`Solution::parse`]. Returns `None` if the part has no such implementation.
+    fn solve_with(
+        &self,
+        part: u8,
+        name: &str,
+        input: &ParsedInput,
+    ) -> Option<Result<usize, AocError>>;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String>;
+
+    /// Render an input returned by this solution's [`Solution::parse`] like [`Puzzle::render`].
+    fn render(
+        &self,
+        input: &ParsedInput,
+        rows: RangeInclusive<usize>,
+        cols: RangeInclusive<usize>,
+    ) -> Option<String>;
+}
+
+impl<P> Solution for P
+where
+    P: Puzzle + Sync,
+    P::Input: Send + Sync + 'static,
+{
+    fn year(&self) -> u16 {
+        P::YEAR
+    }
+
+    fn day(&self) -> u16 {
+        P::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        P::TITLE
+    }
+
+    fn questions(&self) -> [&'static str; 2] {
+        P::QUESTIONS
+    }
+
+    fn parse(&self, input: &str) -> Result<ParsedInput, AocError> {
+        P::parse(input).map(|parsed| Box::new(parsed) as ParsedInput)
+    }
+
+    fn part1(&self, input: &ParsedInput) -> Result<usize, AocError> {
+        P::part1(downcast_input::<P>(input))
+    }
+
+    fn part2(&self, input: &ParsedInput) -> Result<usize, AocError> {
+        P::part2(downcast_input::<P>(input))
+    }
+
+    fn implementations(&self, part: u8) -> Vec<&'static str> {
+        P::implementations()
+            .into_iter()
+            .filter(|implementation| implementation.part == part)
+            .map(|implementation| implementation.name)
+            .collect()
+    }
+
+    fn solve_with(
+        &self,
+        part: u8,
+        name: &str,
+        input: &ParsedInput,
+    ) -> Option<Result<usize, AocError>> {
+        P::implementations()
+            .into_iter()
+            .find(|implementation| implementation.part == part && implementation.name == name)
+            .map(|implementation| (implementation.solve)(downcast_input::<P>(input)))
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        P::generate(rng, size)
+    }
+
+    fn render(
+        &self,
+        input: &ParsedInput,
+        rows: RangeInclusive<usize>,
+        cols: RangeInclusive<usize>,
+    ) -> Option<String> {
+        P::render(downcast_input::<P>(input), rows, cols)
+    }
+}
+
+fn downcast_input<P>(input: &ParsedInput) -> &P::Input
+where
+    P: Puzzle,
+    P::Input: 'static,
+{
+    input.downcast_ref().unwrap_or_else(|| {
+        panic!(
+            "Input for {} day {} was parsed by another solution",
+            P::YEAR,
+            P::DAY
+        )
+    })
+}
@@ -0,0 +1,126 @@
+use std::{fmt, sync::OnceLock};
+
+/// How much detail a trace message gives, from the least to the most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// A few messages per part, like intermediate results
+    Info,
+    /// A message per item of input, like each parsed line
+    Debug,
+    /// A message per step of a solver's inner loop
+    Trace,
+}
+
+impl Level {
+    /// The level enabled by giving `-v` this many times, if any.
+    pub fn from_verbosity(count: usize) -> Option<Level> {
+        match count {
+            0 => None,
+            1 => Some(Level::Info),
+            2 => Some(Level::Debug),
+            _ => Some(Level::Trace),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        f.pad(name)
+    }
+}
+
+/// Which trace messages are emitted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    /// The most detailed level emitted
+    pub level: Level,
+    /// The days to emit messages for, or every day if empty
+    pub days: Vec<u16>,
+}
+
+impl Filter {
+    pub fn allows(&self, day: u16, level: Level) -> bool {
+        level <= self.level && (self.days.is_empty() || self.days.contains(&day))
+    }
+}
+
+/// Where emitted trace messages go, given the day and level of each.
+pub type Sink = fn(u16, Level, fmt::Arguments);
+
+static TRACER: OnceLock<(Filter, Sink)> = OnceLock::new();
+
+/// Start emitting the messages allowed by `filter` to `sink`. Tracing is disabled until this is
+/// called, and can only be enabled once.
+pub fn init(filter: Filter, sink: Sink) {
+    let _ = TRACER.set((filter, sink));
+}
+
+/// Whether messages for `day` at `level` are emitted. Checked by [`trace!`](crate::trace!) before
+/// the message is formatted, so disabled messages cost no more than this check.
+#[inline]
+pub fn enabled(day: u16, level: Level) -> bool {
+    TRACER
+        .get()
+        .is_some_and(|(filter, _)| filter.allows(day, level))
+}
+
+#[doc(hidden)]
+pub fn emit(day: u16, level: Level, message: fmt::Arguments) {
+    if let Some((_, sink)) = TRACER.get() {
+        sink(day, level, message);
+    }
+}
+
+/// Emit a trace message for a day at a level, formatted like `format!` if it is enabled:
+///
+/// ```
+/// use aoc2023::{trace, trace::Level};
+///
+/// let (step_count, node) = (3, "ZZZ");
+/// trace!(8, Level::Trace, "step: {step_count}, node: {node}");
+/// ```
+#[macro_export]
+macro_rules! trace {
+    ($day:expr, $level:expr, $($arg:tt)+) => {{
+        let (day, level) = ($day, $level);
+        if $crate::trace::enabled(day, level) {
+            $crate::trace::emit(day, level, format_args!($($arg)+));
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trace::{Filter, Level};
+
+    #[test]
+    fn test_filter_allows() {
+        let filter = Filter {
+            level: Level::Debug,
+            days: Vec::new(),
+        };
+        assert!(filter.allows(2, Level::Info));
+        assert!(filter.allows(8, Level::Debug));
+        assert!(!filter.allows(8, Level::Trace));
+
+        let filter = Filter {
+            level: Level::Trace,
+            days: vec![8],
+        };
+        assert!(filter.allows(8, Level::Trace));
+        assert!(!filter.allows(2, Level::Info));
+    }
+
+    #[test]
+    fn test_level_from_verbosity() {
+        assert_eq!(None, Level::from_verbosity(0));
+        assert_eq!(Some(Level::Info), Level::from_verbosity(1));
+        assert_eq!(Some(Level::Debug), Level::from_verbosity(2));
+        assert_eq!(Some(Level::Trace), Level::from_verbosity(5));
+    }
+}
@@ -0,0 +1,265 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Output},
+    time::{Duration, SystemTime},
+};
+
+use crate::{scaffold::module_path, AocError};
+
+/// How often the watched files are checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The answer to a part from one run, or the error it failed with.
+pub type Answer = Result<usize, String>;
+
+/// The last modified time of every file in `paths`, including the files in any directories.
+/// Paths that don't exist are left out, so creating them counts as a change.
+pub fn modified_times(paths: &[PathBuf]) -> BTreeMap<PathBuf, SystemTime> {
+    let mut times = BTreeMap::new();
+    let mut pending = paths.to_vec();
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(&path) {
+                pending.extend(entries.filter_map(|entry| Some(entry.ok()?.path())));
+            }
+        } else if let Ok(modified) = metadata.modified() {
+            times.insert(path, modified);
+        }
+    }
+    times
+}
+
+/// The files that were added, removed or modified between two calls to [`modified_times`].
+pub fn changed_files<'a>(
+    before: &'a BTreeMap<PathBuf, SystemTime>,
+    after: &'a BTreeMap<PathBuf, SystemTime>,
+) -> Vec<&'a Path> {
+    let mut changed: Vec<&Path> = after
+        .iter()
+        .filter(|(path, modified)| before.get(*path) != Some(modified))
+        .map(|(path, _)| path.as_path())
+        .chain(
+            before
+                .keys()
+                .filter(|path| !after.contains_key(*path))
+                .map(PathBuf::as_path),
+        )
+        .collect();
+    changed.sort();
+    changed
+}
+
+/// Read the answers to each part from the records printed by `--format json`, with `None` for a
+/// part that has no record.
+pub fn parse_answers(json: &str) -> [Option<Answer>; 2] {
+    let mut answers = [None, None];
+    for record in json
+        .lines()
+        .filter(|line| line.trim_start().starts_with('{'))
+    {
+        let part = json_field(record, "part").and_then(|part| part.parse::<usize>().ok());
+        let Some(slot) = part.and_then(|part| answers.get_mut(part.wrapping_sub(1))) else {
+            continue;
+        };
+        *slot = match (json_field(record, "answer"), json_field(record, "error")) {
+            (Some(answer), _) if answer != "null" => answer.parse().ok().map(Ok),
+            (_, Some(error)) if error != "null" => Some(Err(json_unescape(error))),
+            _ => None,
+        };
+    }
+    answers
+}
+
+/// The raw value of a field in a record on a single line, such as `288` or `"no input"`. The
+/// error is the last field of each record, so a string value runs to the closing brace.
+fn json_field<'a>(record: &'a str, name: &str) -> Option<&'a str> {
+    let key = format!("\"{name}\": ");
+    let start = record.find(&key)? + key.len();
+    let rest = &record[start..];
+    let end = match rest.starts_with('"') {
+        true => rest.rfind('"').filter(|&end| end > 0)? + 1,
+        false => rest.find([',', '}']).unwrap_or(rest.len()),
+    };
+    Some(&rest[..end])
+}
+
+fn json_unescape(value: &str) -> String {
+    let value = value.strip_prefix('"').unwrap_or(value);
+    let value = value.strip_suffix('"').unwrap_or(value);
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('t') => unescaped.push('\t'),
+            Some('u') => {
+                let code: String = chars.by_ref().take(4).collect();
+                let c = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32);
+                unescaped.push(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            Some(c) => unescaped.push(c),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+/// A line for each part comparing its answer with the one from the previous run, if there was one.
+pub fn diff_answers(
+    previous: Option<&[Option<Answer>; 2]>,
+    current: &[Option<Answer>; 2],
+) -> Vec<String> {
+    let describe = |answer: &Option<Answer>| match answer {
+        Some(Ok(answer)) => answer.to_string(),
+        Some(Err(e)) => format!("error ({e})"),
+        None => String::from("no answer"),
+    };
+    (1..)
+        .zip(current)
+        .map(|(part, answer)| {
+            let now = describe(answer);
+            match previous.map(|previous| &previous[part - 1]) {
+                None => format!("Part {part}: {now}"),
+                Some(before) if before == answer => format!("Part {part}: {now} (unchanged)"),
+                Some(before) => format!("Part {part}: {} -> {now}", describe(before)),
+            }
+        })
+        .collect()
+}
+
+/// Run `cargo` with `args` from the current directory, capturing its output.
+fn cargo(args: &[&str]) -> Result<Output, AocError> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    process::Command::new(&cargo)
+        .args(args)
+        .output()
+        .map_err(|source| AocError::Io {
+            path: PathBuf::from(cargo),
+            source,
+        })
+}
+
+/// The outcome of rebuilding and running a day and its tests.
+pub struct Check {
+    pub answers: [Option<Answer>; 2],
+    /// Any build errors, the summary of the tests and any test failures
+    pub messages: Vec<String>,
+}
+
+/// Rebuild and run a day and its tests, which check it against its fixtures. Building in a new
+/// process means changes to the day's source are picked up as well as changes to its input.
+pub fn check(year: u16, day: u16) -> Result<Check, AocError> {
+    let mut messages = Vec::new();
+    let (year_arg, day_arg) = (year.to_string(), day.to_string());
+    let mut args = vec!["run", "--quiet"];
+    if !cfg!(debug_assertions) {
+        args.push("--release");
+    }
+    args.extend(["--", "--year", &year_arg, &day_arg, "--format", "json"]);
+    let output = cargo(&args)?;
+    if !output.status.success() {
+        messages.extend(
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .map(String::from),
+        );
+    }
+    let answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+
+    let filter = format!("{}::", module_path(year, day));
+    let output = cargo(&["test", "--quiet", "--lib", &filter])?;
+    let summary: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| line.starts_with("test result:") || line.contains("panicked"))
+        .map(String::from)
+        .collect();
+    match summary.is_empty() {
+        // The tests didn't build, so show why
+        true => messages.extend(
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .map(String::from),
+        ),
+        false => messages.extend(summary),
+    }
+    Ok(Check { answers, messages })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, env, fs, path::Path};
+
+    use crate::watch::{changed_files, diff_answers, modified_times, parse_answers};
+
+    #[test]
+    fn test_parse_answers() {
+        let json = r#"[
+  {"year": 2023, "day": 6, "part": 1, "answer": 288, "duration_ns": 1500, "error": null},
+  {"year": 2023, "day": 6, "part": 2, "answer": null, "duration_ns": 0, "error": "no \"input\", sorry"}
+]"#;
+        assert_eq!(
+            [
+                Some(Ok(288)),
+                Some(Err(String::from("no \"input\", sorry")))
+            ],
+            parse_answers(json)
+        );
+        assert_eq!([None, None], parse_answers("[]"));
+        assert_eq!([None, None], parse_answers(""));
+    }
+
+    #[test]
+    fn test_diff_answers() {
+        let first = [Some(Ok(288)), Some(Err(String::from("oops")))];
+        assert_eq!(
+            vec!["Part 1: 288", "Part 2: error (oops)"],
+            diff_answers(None, &first)
+        );
+        let second = [Some(Ok(288)), Some(Ok(71503))];
+        assert_eq!(
+            vec!["Part 1: 288 (unchanged)", "Part 2: error (oops) -> 71503"],
+            diff_answers(Some(&first), &second)
+        );
+        assert_eq!(
+            vec!["Part 1: 288 -> no answer", "Part 2: 71503 (unchanged)"],
+            diff_answers(Some(&second), &[None, Some(Ok(71503))])
+        );
+    }
+
+    #[test]
+    fn test_modified_times() {
+        let dir = env::temp_dir().join(format!("aoc2023-watch-{}", std::process::id()));
+        let fixtures = dir.join("fixtures");
+        let input = dir.join("day06.txt");
+        fs::create_dir_all(&fixtures).unwrap();
+        fs::write(fixtures.join("part1-1.txt"), "a").unwrap();
+        let paths = [input.clone(), fixtures.clone()];
+
+        let before = modified_times(&paths);
+        assert_eq!(1, before.len());
+        assert!(changed_files(&before, &modified_times(&paths)).is_empty());
+
+        fs::write(&input, "b").unwrap();
+        fs::remove_file(fixtures.join("part1-1.txt")).unwrap();
+        let after = modified_times(&paths);
+        assert_eq!(
+            vec![input.as_path(), fixtures.join("part1-1.txt").as_path()],
+            changed_files(&before, &after)
+        );
+        assert_eq!(
+            vec![Path::new(&input)],
+            changed_files(&BTreeMap::new(), &after)
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
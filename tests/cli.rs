@@ -0,0 +1,33 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+// Run the binary with `args`, giving it `input` on stdin.
+fn run(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc2023"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_json_exit_code() {
+    let output = run(&["4", "-", "--format", "json"], "Card 1: 1 | 1\n");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains(r#""answer": 1"#));
+
+    let output = run(&["4", "-", "--format", "json"], "Card 1: x | 1\n");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains(r#""error": "Malformed"#));
+}